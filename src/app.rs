@@ -1,10 +1,26 @@
 use std::error;
+use std::fs;
+use std::path::Path;
 
-use crate::{game::Game, ui::Ui};
+use rand::{thread_rng, Rng};
+
+use crate::{
+    game::{Game, GameState},
+    preset::Set,
+    replay::Replay,
+    save::Save,
+    ui::Ui,
+};
 
 /// Application result type.
 pub type AppResult<T> = std::result::Result<T, Box<dyn error::Error>>;
 
+/// Where an unfinished battle is kept between launches.
+pub const SAVE_PATH: &str = "pkml_save.json";
+
+/// Where the battle played in the terminal is recorded, rewritten after every turn.
+pub const REPLAY_PATH: &str = "pkml_replay.json";
+
 /// Application.
 #[derive(Debug)]
 pub struct App {
@@ -29,6 +45,44 @@ impl App {
         Self::default()
     }
 
+    /// Starts a new battle between the given teams, player 1's first.
+    pub fn with_teams(teams: [Vec<Set>; 2]) -> Self {
+        Self {
+            games: Game::with_teams(thread_rng().gen(), teams),
+            ..Self::default()
+        }
+    }
+
+    /// Restores the battle saved at [`SAVE_PATH`], or starts a new one if there is none.
+    pub fn resume() -> AppResult<Self> {
+        if !Path::new(SAVE_PATH).exists() {
+            return Ok(Self::new());
+        }
+        Ok(Self {
+            games: Save::load(SAVE_PATH)?.restore()?,
+            ..Self::default()
+        })
+    }
+
+    /// Saves the battle if it is still in progress, otherwise clears any old save.
+    pub fn save(&self) -> AppResult<()> {
+        if let GameState::Completed(_) = self.games.state {
+            if Path::new(SAVE_PATH).exists() {
+                fs::remove_file(SAVE_PATH)?;
+            }
+            Ok(())
+        } else {
+            Save::from(&self.games).save(SAVE_PATH)
+        }
+    }
+
+    /// Runs the turn for the input player 1 just queued and records the battle so far, so the
+    /// replay survives a crash as well as a clean exit.
+    pub fn execute_turn(&mut self) -> AppResult<()> {
+        self.games.execute_turn();
+        Replay::from(&self.games).save(REPLAY_PATH)
+    }
+
     /// Handles the tick event of the terminal.
     pub fn tick(&self) {}
 
@@ -0,0 +1,299 @@
+use crate::game::{GameResult, HazardId, WeatherId};
+use crate::moves::MoveId;
+use crate::player::Player;
+use crate::preset::PokeId;
+use crate::stat::StatId;
+use crate::status::Status;
+use crate::trigger::{Ability, Item};
+use serde::{Deserialize, Serialize};
+
+/// Everything that can happen during a battle. Players are referred to by their index into
+/// `Game.players`, render with [`BattleEvent::render`] to get the log text.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
+pub enum BattleEvent {
+    BattleStart,
+    /// blank line between actions
+    ActionEnd,
+    MoveUsed {
+        player: usize,
+        mon: PokeId,
+        move_id: MoveId,
+    },
+    /// the move failed its accuracy roll
+    MoveMissed {
+        player: usize,
+        mon: PokeId,
+    },
+    CriticalHit,
+    MoveFailed,
+    /// the target is immune to the move's type
+    NoEffect {
+        player: usize,
+        mon: PokeId,
+    },
+    AbilityActivated {
+        player: usize,
+        mon: PokeId,
+        ability: Ability,
+    },
+    ItemActivated {
+        player: usize,
+        mon: PokeId,
+        item: Item,
+    },
+    ItemRemoved {
+        player: usize,
+        mon: PokeId,
+        item: Item,
+    },
+    /// the mon traded items with its target
+    ItemsSwapped {
+        player: usize,
+        mon: PokeId,
+    },
+    ItemObtained {
+        player: usize,
+        mon: PokeId,
+        item: Item,
+    },
+    ItemStolen {
+        player: usize,
+        mon: PokeId,
+        item: Item,
+    },
+    /// a status prevented the mon from moving
+    MoveSkipped {
+        mon: PokeId,
+        cause: Status,
+    },
+    Withdraw {
+        player: usize,
+        mon: PokeId,
+    },
+    SelectReplacement {
+        player: usize,
+    },
+    SwitchPrompt {
+        player: usize,
+    },
+    Switch {
+        player: usize,
+        mon: PokeId,
+    },
+    Damage {
+        player: usize,
+        mon: PokeId,
+        amount: i32,
+    },
+    Heal {
+        player: usize,
+        mon: PokeId,
+        amount: i32,
+    },
+    Faint {
+        player: usize,
+        mon: PokeId,
+    },
+    StatusInflicted {
+        player: usize,
+        mon: PokeId,
+        status: Status,
+    },
+    /// a status wore off on its own
+    StatusEnded {
+        mon: PokeId,
+        status: Status,
+    },
+    StatusCured {
+        player: usize,
+        mon: PokeId,
+    },
+    StatChanged {
+        player: usize,
+        mon: PokeId,
+        stat: StatId,
+        stages: i32,
+    },
+    /// the stat was already at its limit in the direction of the change
+    StatUnchanged {
+        player: usize,
+        mon: PokeId,
+        stat: StatId,
+        raise: bool,
+    },
+    HazardSet {
+        player: usize,
+        hazard: HazardId,
+    },
+    HazardsCleared {
+        player: usize,
+    },
+    WeatherSet(WeatherId),
+    GameEnd(GameResult),
+}
+
+impl BattleEvent {
+    /// formats the event as a line of the battle log
+    pub fn render(&self, players: &[Player]) -> String {
+        match *self {
+            Self::BattleStart => String::from("Players sent out their starters!"),
+            Self::ActionEnd => String::new(),
+            Self::MoveUsed {
+                player,
+                mon,
+                move_id,
+            } => format!("{}'s {} used {}", players[player], mon, move_id),
+            Self::MoveMissed { player, mon } => {
+                format!("{}'s {}'s attack missed!", players[player], mon)
+            }
+            Self::CriticalHit => String::from("A critical hit!"),
+            Self::MoveFailed => String::from("But it failed!"),
+            Self::NoEffect { player, mon } => {
+                format!("It doesn't affect {}'s {}...", players[player], mon)
+            }
+            Self::AbilityActivated {
+                player,
+                mon,
+                ability,
+            } => match ability {
+                Ability::Pressure => {
+                    format!("{}'s {} is exerting its Pressure!", players[player], mon)
+                }
+                Ability::Levitate => format!(
+                    "{}'s {} avoided the attack with Levitate!",
+                    players[player], mon
+                ),
+                Ability::RoughSkin => format!(
+                    "{}'s {}'s Rough Skin hurt its attacker!",
+                    players[player], mon
+                ),
+                Ability::VoltAbsorb | Ability::WaterAbsorb => format!(
+                    "{}'s {} absorbed the attack with {}!",
+                    players[player], mon, ability
+                ),
+                _ => format!("{}'s {}'s {} activated!", players[player], mon, ability),
+            },
+            Self::MoveSkipped { mon, cause } => match cause {
+                Status::Paralyse => format!("{} was full para", mon),
+                Status::Sleep => format!("{} was sleeping", mon),
+                Status::Freeze => format!("{} is frozen", mon),
+                Status::Confusion => format!("{} hit itself in its confusion!", mon),
+                Status::Flinch => format!("{} flinched", mon),
+                _ => format!("{} couldn't move", mon),
+            },
+            Self::Withdraw { player, mon } => format!("{} withdraws {}", players[player], mon),
+            Self::SelectReplacement { player } => format!("{} selects new mon", players[player]),
+            Self::SwitchPrompt { player } => {
+                format!("{} selects a pokemon to switch to", players[player])
+            }
+            Self::Switch { player, mon } => format!("{} sends out {}", players[player], mon),
+            Self::Damage {
+                player,
+                mon,
+                amount,
+            } => format!("{}'s {} lost {} hp", players[player], mon, amount),
+            Self::Heal {
+                player,
+                mon,
+                amount,
+            } => format!("{}'s {} gained {} hp", players[player], mon, amount),
+            Self::Faint { player, mon } => format!("{}'s {} fainted!", players[player], mon),
+            Self::ItemActivated { player, mon, item } => match item {
+                Item::RockyHelmet => format!(
+                    "{}'s {}'s Rocky Helmet hurt its attacker!",
+                    players[player], mon
+                ),
+                Item::LifeOrb => format!("{}'s {} was hurt by its Life Orb", players[player], mon),
+                Item::FocusSash => format!(
+                    "{}'s {} hung on using its Focus Sash!",
+                    players[player], mon
+                ),
+                Item::WhiteHerb => format!(
+                    "{}'s {}'s White Herb restored its stats!",
+                    players[player], mon
+                ),
+                item if item.is_berry() => {
+                    format!("{}'s {} ate its {}!", players[player], mon, item)
+                }
+                _ => format!("{}'s {} used its {}", players[player], mon, item),
+            },
+            Self::ItemRemoved { player, mon, item } => {
+                format!("{}'s {}'s {} was knocked off!", players[player], mon, item)
+            }
+            Self::ItemsSwapped { player, mon } => format!(
+                "{}'s {} switched items with its target!",
+                players[player], mon
+            ),
+            Self::ItemObtained { player, mon, item } => {
+                format!("{}'s {} obtained a {}", players[player], mon, item)
+            }
+            Self::ItemStolen { player, mon, item } => {
+                format!("{}'s {} stole a {}!", players[player], mon, item)
+            }
+            Self::StatusInflicted {
+                player,
+                mon,
+                status: Status::FlashFire,
+            } => format!(
+                "{}'s {}'s Fire moves were powered up!",
+                players[player], mon
+            ),
+            Self::StatusInflicted {
+                player,
+                mon,
+                status: Status::Drowsy,
+            } => format!("{}'s {} grew drowsy!", players[player], mon),
+            Self::StatusInflicted {
+                player,
+                mon,
+                status,
+            } => format!("{}'s {} was {}", players[player], mon, status),
+            Self::StatusEnded { mon, status } => match status {
+                Status::Sleep => format!("{} woke up!", mon),
+                Status::Freeze => format!("{} thawed!", mon),
+                Status::Confusion => format!("{} snapped out of confusion", mon),
+                _ => format!("{} is no longer {}", mon, status),
+            },
+            Self::StatusCured { player, mon } => {
+                format!("{}'s {} was cured of status", players[player], mon)
+            }
+            Self::StatChanged {
+                player,
+                mon,
+                stat,
+                stages,
+            } => format!(
+                "{}'s {} {} was {}",
+                players[player],
+                mon,
+                stat,
+                if stages > 0 { "raised" } else { "lowered" }
+            ),
+            Self::StatUnchanged {
+                player,
+                mon,
+                stat,
+                raise,
+            } => format!(
+                "{}'s {} {} won't go any {}!",
+                players[player],
+                mon,
+                stat,
+                if raise { "higher" } else { "lower" }
+            ),
+            Self::HazardSet { player, hazard } => {
+                format!("{} was placed on {}'s field", hazard, players[player])
+            }
+            Self::HazardsCleared { player } => {
+                format!("hazards were cleared from {}'s field", players[player])
+            }
+            Self::WeatherSet(weather) => String::from(match weather {
+                WeatherId::Sand => "A sandstorm kicked up!",
+                WeatherId::Hail => "Hail starts",
+                WeatherId::Rain => "Rain starts",
+                WeatherId::Sun => "The sunlight turned harsh!",
+            }),
+            Self::GameEnd(_) => String::from("game finished"),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+/// A damage multiplier kept as a fraction so it truncates the way the games do.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Modifier(pub i32, pub i32);
+
+impl Modifier {
+    pub const NEUTRAL: Self = Self(1, 1);
+
+    pub fn apply(&self, value: i32) -> i32 {
+        value * self.0 / self.1
+    }
+}
+
+impl Default for Modifier {
+    fn default() -> Self {
+        Self::NEUTRAL
+    }
+}
+
+/// Inputs to the Gen 4 damage formula
+///
+/// `((((Level * 2 / 5 + 2) * Power * Atk / 50) / Def) * Mod1 + 2) * CH * Mod2 * R / 100
+/// * STAB * Type1 * Type2 * Mod3`
+///
+/// with every multiplication truncated before the next. `attack` and `defense` are the stats
+/// after stages, the modifier lists hold item and ability effects.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DamageCalc {
+    pub level: i32,
+    pub base_power: i32,
+    pub attack: i32,
+    pub defense: i32,
+    /// base power modifiers, e.g. Muscle Band or plates
+    pub power_mods: Vec<Modifier>,
+    /// attacking stat modifiers, e.g. Choice Band
+    pub attack_mods: Vec<Modifier>,
+    /// only set for physical moves
+    pub burn: bool,
+    pub weather: Modifier,
+    pub flash_fire: bool,
+    pub crit: bool,
+    /// e.g. Life Orb
+    pub mod2: Vec<Modifier>,
+    pub stab: bool,
+    /// effectiveness against each defending type, see [`crate::poketype::Type::calc_eff_split`]
+    pub effectiveness: Vec<f32>,
+    /// e.g. Expert Belt or resist berries
+    pub mod3: Vec<Modifier>,
+}
+
+impl Default for DamageCalc {
+    fn default() -> Self {
+        Self {
+            level: 100,
+            base_power: 0,
+            attack: 1,
+            defense: 1,
+            power_mods: Vec::new(),
+            attack_mods: Vec::new(),
+            burn: false,
+            weather: Modifier::NEUTRAL,
+            flash_fire: false,
+            crit: false,
+            mod2: Vec::new(),
+            stab: false,
+            effectiveness: vec![1.0],
+            mod3: Vec::new(),
+        }
+    }
+}
+
+impl DamageCalc {
+    pub const MIN_ROLL: i32 = 85;
+    pub const MAX_ROLL: i32 = 100;
+
+    /// damage dealt with the given random roll, between 85 and 100 inclusive
+    pub fn calc(&self, roll: i32) -> i32 {
+        if self.effectiveness.contains(&0.0) {
+            return 0;
+        }
+
+        let power = apply_all(&self.power_mods, self.base_power);
+        let attack = apply_all(&self.attack_mods, self.attack);
+
+        let mut damage = (self.level * 2 / 5 + 2) * power * attack / 50 / self.defense;
+
+        // mod1
+        if self.burn {
+            damage /= 2;
+        }
+        damage = self.weather.apply(damage);
+        if self.flash_fire {
+            damage = damage * 3 / 2;
+        }
+
+        damage += 2;
+        if self.crit {
+            damage *= 2;
+        }
+        damage = apply_all(&self.mod2, damage);
+        damage = damage * roll / 100;
+        if self.stab {
+            damage = damage * 3 / 2;
+        }
+        for eff in &self.effectiveness {
+            damage = (damage as f32 * eff) as i32;
+        }
+        damage = apply_all(&self.mod3, damage);
+
+        // anything that connects does at least 1
+        damage.max(1)
+    }
+
+    /// lowest and highest possible damage
+    pub fn range(&self) -> (i32, i32) {
+        (self.calc(Self::MIN_ROLL), self.calc(Self::MAX_ROLL))
+    }
+}
+
+fn apply_all(mods: &[Modifier], value: i32) -> i32 {
+    mods.iter().fold(value, |acc, modifier| modifier.apply(acc))
+}
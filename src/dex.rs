@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::app::AppResult;
+use crate::bounded_i32::BoundedI32;
+use crate::game::{Damage, Effect};
+use crate::moves::{Move, MoveId, Mtype};
+use crate::poketype::{Poketype, Type};
+use crate::preset::{PokeId, Set};
+use crate::selvec::PlayerId;
+use crate::stat::Spread;
+use crate::trigger::{Ability, Item};
+
+/// Directory holding `species.json` and `moves.json`.
+pub const DATA_DIR: &str = "data";
+
+/// Environment variable pointing at a data directory to use instead of the shipped one.
+pub const DATA_ENV: &str = "PKML_DATA";
+
+/// the shipped data, used when no data directory can be found
+const MOVES_JSON: &str = include_str!("../data/moves.json");
+const SPECIES_JSON: &str = include_str!("../data/species.json");
+
+/// Name of the move every mon falls back on once it's out of pp.
+pub const STRUGGLE: &str = "Struggle";
+
+static DEX: OnceLock<Dex> = OnceLock::new();
+
+/// Every species and move the game knows about, see [`locate`] for where it is loaded from.
+#[derive(Debug)]
+pub struct Dex {
+    pub species: Vec<Species>,
+    pub moves: Vec<Move>,
+    move_names: Vec<String>,
+    /// keyed by [`to_id`] of the name
+    species_ids: HashMap<String, PokeId>,
+    move_ids: HashMap<String, MoveId>,
+    struggle: MoveId,
+}
+
+#[derive(Debug, Clone)]
+pub struct Species {
+    pub name: String,
+    pub poketype: Poketype,
+    /// HP, Atk, Def, SpA, SpD, Spe
+    pub base_stats: [i32; 6],
+    /// set used when only the species is given
+    pub preset: Set,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MoveData {
+    name: String,
+    #[serde(rename = "type")]
+    poke_type: Type,
+    category: Mtype,
+    base_power: Option<i32>,
+    pp: i32,
+    #[serde(default)]
+    priority: i8,
+    #[serde(default)]
+    effects: Vec<Effect>,
+    freq: Option<f32>,
+    #[serde(default = "foe")]
+    target: PlayerId,
+    /// left out for moves that never miss
+    accuracy: Option<f32>,
+    #[serde(default)]
+    crit_stage: i8,
+    #[serde(default)]
+    contact: bool,
+    #[serde(default)]
+    type_immunity: bool,
+}
+
+fn foe() -> PlayerId {
+    PlayerId::Inactive
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpeciesData {
+    name: String,
+    types: Vec<Type>,
+    base_stats: [i32; 6],
+    preset: SetData,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SetData {
+    ability: Ability,
+    item: Option<Item>,
+    moves: Vec<String>,
+    #[serde(default)]
+    spread: Spread,
+}
+
+/// the loaded game data, panics if the data files are missing or invalid. Call [`init`] first
+/// to handle that as an error.
+pub fn dex() -> &'static Dex {
+    DEX.get_or_init(|| locate().unwrap_or_else(|err| panic!("invalid game data: {}", err)))
+}
+
+/// loads and validates the game data if it hasn't been already
+pub fn init() -> AppResult<&'static Dex> {
+    if let Some(dex) = DEX.get() {
+        return Ok(dex);
+    }
+    let dex = locate()?;
+    Ok(DEX.get_or_init(|| dex))
+}
+
+/// Loads the data from the directory in [`DATA_ENV`] if it is set. Otherwise the first
+/// [`DATA_DIR`] found next to the executable or in the crate is used, falling back to the
+/// copy built into the binary so the working directory never matters.
+pub fn locate() -> AppResult<Dex> {
+    if let Some(dir) = env::var_os(DATA_ENV) {
+        return Dex::load(Path::new(&dir));
+    }
+    let exe_dir = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(DATA_DIR)));
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(DATA_DIR);
+    match exe_dir
+        .into_iter()
+        .chain([crate_dir])
+        .find(|dir| dir.join("moves.json").is_file())
+    {
+        Some(dir) => Dex::load(&dir),
+        None => Dex::bundled(),
+    }
+}
+
+impl Dex {
+    /// reads `moves.json` and `species.json` from `dir`
+    pub fn load(dir: &Path) -> AppResult<Self> {
+        Self::build(
+            read(&dir.join("moves.json"))?,
+            read(&dir.join("species.json"))?,
+        )
+    }
+
+    /// the data shipped with the crate, built into the binary
+    pub fn bundled() -> AppResult<Self> {
+        Self::build(
+            parse("moves.json", MOVES_JSON)?,
+            parse("species.json", SPECIES_JSON)?,
+        )
+    }
+
+    fn build(moves: Vec<MoveData>, species: Vec<SpeciesData>) -> AppResult<Self> {
+        let mut dex = Self {
+            species: Vec::new(),
+            moves: Vec::new(),
+            move_names: Vec::new(),
+            species_ids: HashMap::new(),
+            move_ids: HashMap::new(),
+            struggle: MoveId::default(),
+        };
+
+        for data in moves {
+            validate_move(&data).map_err(|err| format!("move {}: {}", data.name, err))?;
+            let id = MoveId(dex.moves.len());
+            if dex.move_ids.insert(to_id(&data.name), id).is_some() {
+                return Err(format!("move {} is defined twice", data.name).into());
+            }
+            dex.moves.push(Move {
+                id,
+                pp: BoundedI32::zero(data.pp),
+                damage_type: data.category,
+                poke_type: data.poke_type,
+                base_power: data.base_power,
+                priority: data.priority,
+                effects: data.effects,
+                freq: data.freq,
+                target: data.target,
+                accuracy: data.accuracy,
+                crit_stage: data.crit_stage,
+                contact: data.contact,
+                type_immunity: data.type_immunity,
+            });
+            dex.move_names.push(data.name);
+        }
+        dex.struggle = dex
+            .move_id(STRUGGLE)
+            .ok_or(format!("missing {} in moves.json", STRUGGLE))?;
+
+        for data in species {
+            let id = PokeId(dex.species.len());
+            let species = dex
+                .resolve_species(id, data)
+                .map_err(|err| format!("species {}", err))?;
+            if dex.species_ids.insert(to_id(&species.name), id).is_some() {
+                return Err(format!("species {} is defined twice", species.name).into());
+            }
+            dex.species.push(species);
+        }
+
+        Ok(dex)
+    }
+
+    pub fn species(&self, id: PokeId) -> &Species {
+        &self.species[id.0]
+    }
+
+    pub fn species_id(&self, name: &str) -> Option<PokeId> {
+        self.species_ids.get(&to_id(name)).copied()
+    }
+
+    pub fn move_name(&self, id: MoveId) -> &str {
+        &self.move_names[id.0]
+    }
+
+    pub fn move_id(&self, name: &str) -> Option<MoveId> {
+        self.move_ids.get(&to_id(name)).copied()
+    }
+
+    pub fn struggle(&self) -> &Move {
+        &self.moves[self.struggle.0]
+    }
+
+    fn resolve_species(&self, id: PokeId, data: SpeciesData) -> Result<Species, String> {
+        let poketype = match data.types[..] {
+            [single] => Poketype::Mono(single),
+            [first, second] => Poketype::Dual([first, second]),
+            _ => return Err(format!("{}: needs one or two types", data.name)),
+        };
+        if data.base_stats.iter().any(|stat| *stat <= 0) {
+            return Err(format!("{}: base stats must be positive", data.name));
+        }
+
+        let moves = self
+            .resolve_moves(&data.preset.moves)
+            .map_err(|err| format!("{}: {}", data.name, err))?;
+        data.preset
+            .spread
+            .validate()
+            .map_err(|err| format!("{}: {}", data.name, err))?;
+
+        Ok(Species {
+            name: data.name,
+            poketype,
+            base_stats: data.base_stats,
+            preset: Set {
+                species: id,
+                ability: data.preset.ability,
+                item: data.preset.item,
+                moves,
+                spread: data.preset.spread,
+            },
+        })
+    }
+
+    /// looks up a moveset by name, a set has one to four moves
+    pub fn resolve_moves(&self, names: &[String]) -> Result<Vec<MoveId>, String> {
+        if names.is_empty() || names.len() > 4 {
+            return Err(format!("has {} moves, expected 1 to 4", names.len()));
+        }
+        names
+            .iter()
+            .map(|name| {
+                self.move_id(name)
+                    .ok_or_else(|| format!("unknown move {}", name))
+            })
+            .collect()
+    }
+}
+
+/// lowercase letters and digits only, so "U-turn", "U turn" and "uturn" all find the same move
+pub fn to_id(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn read<T: for<'de> Deserialize<'de>>(path: &Path) -> AppResult<T> {
+    let data = fs::read_to_string(path)
+        .map_err(|err| format!("couldn't read {}: {}", path.display(), err))?;
+    parse(&path.display().to_string(), &data)
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(name: &str, data: &str) -> AppResult<T> {
+    Ok(serde_json::from_str(data).map_err(|err| format!("{}: {}", name, err))?)
+}
+
+fn validate_move(data: &MoveData) -> Result<(), String> {
+    if data.pp <= 0 {
+        return Err(String::from("pp must be positive"));
+    }
+    if data.base_power.is_some_and(|bp| bp <= 0) {
+        return Err(String::from("base power must be positive"));
+    }
+    if data.category == Mtype::Status && data.base_power.is_some() {
+        return Err(String::from("status moves can't have a base power"));
+    }
+    if data.accuracy.is_some_and(|acc| acc <= 0.0 || acc > 1.0) {
+        return Err(String::from("accuracy must be in (0, 1]"));
+    }
+    if data.freq.is_some_and(|freq| freq <= 0.0 || freq > 1.0) {
+        return Err(String::from("freq must be in (0, 1]"));
+    }
+    if data.effects.iter().any(|effect| {
+        matches!(
+            effect,
+            Effect::Heal(_, 0) | Effect::Damage(_, Damage::Fractional(_, 0))
+        )
+    }) {
+        return Err(String::from(
+            "heal and damage fractions must have a nonzero divisor",
+        ));
+    }
+    // these are only produced by the engine itself, switching in particular expects a
+    // valid roster index and a player that chose to switch
+    if data.effects.iter().any(|effect| {
+        matches!(
+            effect,
+            Effect::Switch(_)
+                | Effect::ShowAbility(_)
+                | Effect::ShowItem(_)
+                | Effect::ConsumeItem(_)
+        )
+    }) {
+        return Err(String::from("moves can't use engine-only effects"));
+    }
+    Ok(())
+}
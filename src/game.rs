@@ -1,19 +1,25 @@
+use crate::battle_event::BattleEvent;
 use crate::bounded_i32::BoundedI32;
+use crate::damage::{DamageCalc, Modifier};
 use crate::moves::Mtype;
-use crate::player::HazardBlock;
+use crate::player::{default_team, HazardBlock};
 use crate::poketype::Type;
+use crate::preset::Set;
 use crate::selvec::PlayerId;
 use crate::status::Status;
-use crate::trigger::Item;
+use crate::trigger::Ability;
 use rand::seq::SliceRandom;
-use rand::{thread_rng, Rng};
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::fmt::Display;
 use std::rc::Rc;
 
+use crate::stat::accuracy_fraction;
 use crate::{player::Player, pokemon::Pokemon, selvec::PointerVec, stat::StatId};
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
 pub enum HazardId {
     StealthRock,
     Spikes,
@@ -44,25 +50,38 @@ impl HazardId {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default, Serialize, Deserialize)]
 pub enum WeatherId {
     #[default]
     Sand,
     Rain,
     Hail,
+    Sun,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Game {
     pub players: PointerVec<Player>,
     pub turn_count: i32,
     pub weather: Rc<RefCell<Option<WeatherId>>>,
-    pub log: Vec<Vec<String>>,
+    /// battle events grouped by turn
+    pub log: Vec<Vec<BattleEvent>>,
     pub state: GameState,
     pub prev_state: Vec<GameState>,
+    /// seed the rng was constructed from, kept so a battle can be reproduced
+    pub seed: u64,
+    /// every random roll in the battle is drawn from here, the same generator as `StdRng` but
+    /// one that can be saved mid battle
+    pub rng: ChaCha12Rng,
 }
 
-#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+impl Default for Game {
+    fn default() -> Self {
+        Self::with_seed(thread_rng().gen())
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum GameState {
     #[default]
     TurnStart,
@@ -92,42 +111,68 @@ impl Display for GameState {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum GameResult {
     Winner(usize),
     Tie,
     Incomplete,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MoveSelection {
     Switch(usize),
     Move(usize),
+    /// forced once every move is out of pp
+    Struggle,
 }
 
-fn write_log(log: &mut [Vec<String>], message: String) {
+fn write_log(log: &mut [Vec<BattleEvent>], event: BattleEvent) {
     log.last_mut()
         .expect("attempted to write to empty log")
-        .push(message);
+        .push(event);
 }
 
 impl Game {
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// same seed and same inputs always produce the same battle
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_teams(seed, [default_team(), default_team()])
+    }
+
+    pub fn with_teams(seed: u64, teams: [Vec<Set>; 2]) -> Self {
         let weather = Rc::new(RefCell::new(None));
-        Self {
+        let [team1, team2] = teams;
+        let mut game = Self {
             players: PointerVec::from(vec![
-                Player::new(false, Rc::clone(&weather)),
-                Player::new(true, Rc::clone(&weather)),
+                Player::with_team(false, team1, Rc::clone(&weather)),
+                Player::with_team(true, team2, Rc::clone(&weather)),
             ]),
-            log: vec![vec![String::from("Players sent out their starters!")]],
+            turn_count: 0,
+            log: vec![vec![BattleEvent::BattleStart]],
             weather,
-            ..Default::default()
+            state: GameState::default(),
+            prev_state: Vec::new(),
+            seed,
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        };
+
+        // leads enter the field without a switch action, faster first like replacements
+        game.order_turn_by_speed();
+        for _ in 0..game.players.data.len() {
+            game.exec_switch_in();
+            game.invert_active_player();
         }
+        game.players.active = Some(0);
+        game
     }
 
     pub fn execute_turn(&mut self) {
         match self.state {
             GameState::TurnStart => {
+                self.turn_count += 1;
                 self.log.push(Vec::new());
                 self.input_rand_ai();
                 self.init_turn_order();
@@ -147,25 +192,28 @@ impl Game {
                     self.apply_eot_effects();
                     self.invert_active_player();
                 }
-
-                if self.players[0].roster.active().is_none() {
-                    self.prev_state.push(self.state);
-                    self.state = GameState::AwaitingSwitch;
-                    self.players.active = Some(0);
-                }
-                if self.players[1].roster.active().is_none() {
-                    self.prev_state.push(self.state);
-                    self.state = GameState::AwaitingSwitch;
-                    self.players.active = Some(1);
-                }
+                self.await_replacements();
             }
             GameState::AwaitingSwitch => {
                 self.state = self
                     .prev_state
                     .pop()
                     .expect("gamestate reverted with no prev");
-                self.execute_move();
-                self.invert_active_player();
+                if self.state == GameState::TurnStart {
+                    // the bot picks alongside the human instead of after seeing their pick
+                    if self.players.active == Some(0) && self.players[1].needs_replacement() {
+                        self.input_rand_ai();
+                    }
+                    self.exec_replacements();
+                } else {
+                    // mid turn switches fall through to the next mover
+                    self.execute_move();
+                    if !self.players[PlayerId::Active].has_active() {
+                        // replacement fainted to entry hazards, pick again
+                        self.prev_state.push(self.state);
+                        self.state = GameState::AwaitingSwitch;
+                    }
+                }
             }
             GameState::Completed(_) => {
                 return;
@@ -175,7 +223,7 @@ impl Game {
         let winner = self.check_winner();
         if winner != GameResult::Incomplete {
             self.state = GameState::Completed(winner);
-            write_log(&mut self.log, String::from("game finished"));
+            write_log(&mut self.log, BattleEvent::GameEnd(winner));
         }
 
         match self.state {
@@ -215,19 +263,14 @@ impl Game {
                 _ => {}
             }
 
-            // eot item triggers
-            if let Some(item) = *active_mon.item.borrow() {
-                match item {
-                    Item::Leftovers => effects.push(vec![Effect::Heal(PlayerId::Active, 16)]),
-                    Item::ToxicOrb => {
-                        effects.push(vec![Effect::InflictStatus(PlayerId::Active, Status::Toxic)])
-                    }
-                    _ => {}
-                }
+            // eot ability and item triggers
+            for trigger in active_mon.triggers() {
+                effects.push(trigger.on_residual(active_mon));
             }
 
             // increment counters
-            for status in &[Status::Toxic, Status::Drowsy, Status::Confusion] {
+            // confusion counts down on move attempts instead, see exec_moveskip
+            for status in &[Status::Toxic, Status::Drowsy] {
                 active_mon
                     .status
                     .try_borrow_mut()
@@ -242,10 +285,13 @@ impl Game {
                 .data
                 .remove(&Status::Flinch);
 
-            // calc status effects
+            // calc status effects, poison heal turns poison damage into its residual heal
+            let poison_heal = active_mon.ability == Ability::Poisonheal;
             for status in &[Status::Burn, Status::Poison, Status::Toxic, Status::Drowsy] {
-                if let Some(value) = active_mon.status.borrow().data.get(status) {
+                let value = active_mon.status.borrow().data.get(status).copied();
+                if let Some(value) = value {
                     match status {
+                        Status::Poison | Status::Toxic if poison_heal => {}
                         Status::Burn | Status::Poison => {
                             effects.push(vec![Effect::Damage(
                                 PlayerId::Active,
@@ -255,10 +301,11 @@ impl Game {
                         Status::Toxic => {
                             effects.push(vec![Effect::Damage(
                                 PlayerId::Active,
-                                Damage::Fractional(*value as i32, 16),
+                                Damage::Fractional(value as i32, 16),
                             )]);
                         }
-                        Status::Drowsy if *value == 0 => {
+                        // yawn's drowsiness runs out at the end of the turn after it landed
+                        Status::Drowsy if value == 0 => {
                             effects
                                 .push(vec![Effect::InflictStatus(PlayerId::Active, Status::Sleep)]);
                             active_mon
@@ -277,11 +324,10 @@ impl Game {
     }
 
     pub fn input_rand_ai(&mut self) {
-        let mut rng = thread_rng();
         let choices = self.players[1].list_valid_inputs(&self.state);
         self.players[1]
             .inputs
-            .push(*choices.choose(&mut rng).unwrap());
+            .push(*choices.choose(&mut self.rng).unwrap());
     }
 
     fn check_winner(&self) -> GameResult {
@@ -296,6 +342,7 @@ impl Game {
 
     /// executes the last move input by the active player
     fn execute_move(&mut self) {
+        let player_idx = self.players.idx(PlayerId::Active);
         let player = &self.players[PlayerId::Active];
         match *player
             .inputs
@@ -307,178 +354,409 @@ impl Game {
                 write_log(
                     &mut self.log,
                     if let Some(mon) = player.roster.active() {
-                        format!("{} withdraws {}", player, mon.id)
+                        BattleEvent::Withdraw {
+                            player: player_idx,
+                            mon: mon.id,
+                        }
                     } else {
-                        format!("{} selects new mon", player)
+                        BattleEvent::SelectReplacement { player: player_idx }
                     },
                 );
 
-                let mut effects = vec![Effect::Switch(idx)];
-                effects.extend(self.calc_switch(PlayerId::Active));
+                let mut effects = Vec::new();
+                if let Some(mon) = player.roster.active() {
+                    for trigger in mon.triggers() {
+                        effects.extend(trigger.on_switch_out());
+                    }
+                }
+                effects.push(Effect::Switch(idx));
                 self.apply_effects(effects);
+                // hazards depend on the incoming mon
+                let effects = self.calc_switch(PlayerId::Active);
+                self.apply_effects(effects);
+                self.exec_switch_in();
             }
-            MoveSelection::Move(idx) => {
+            selection => {
                 // check if pokemon can move
-                if let Some(message) = self.exec_moveskip() {
-                    write_log(&mut self.log, message);
+                if self.exec_moveskip() {
                     return;
                 }
 
-                let player = &self.players[PlayerId::Active];
-
-                let active_mon = player
+                let active_mon = self.players[PlayerId::Active]
                     .roster
-                    .active()
+                    .active_mut()
                     .expect("move used with no active mon");
 
+                // struggle has no pp to spend and doesn't lock choice items
+                if let MoveSelection::Move(idx) = selection {
+                    active_mon.moves[idx].pp -= 1;
+                    let choice = active_mon
+                        .item
+                        .borrow()
+                        .is_some_and(|item| item.is_choice());
+                    if choice && active_mon.moves.active.is_none() {
+                        active_mon.moves.active = Some(idx);
+                    }
+                }
+
                 write_log(
                     &mut self.log,
-                    format!(
-                        "{}'s {} used {}",
-                        player, active_mon.id, active_mon.moves[idx].id
-                    ),
+                    BattleEvent::MoveUsed {
+                        player: player_idx,
+                        mon: active_mon.id,
+                        move_id: active_mon
+                            .get_move(&selection)
+                            .expect("move used with switch input")
+                            .id,
+                    },
                 );
 
-                self.apply_effects(self.calc_move());
+                let effects = self.calc_move();
+                self.apply_effects(effects);
+                self.exec_move_end();
+            }
+        }
+        write_log(&mut self.log, BattleEvent::ActionEnd);
+    }
+
+    /// after faints at the end of a turn both players pick their replacements as one decision,
+    /// the human first if they need one
+    fn await_replacements(&mut self) {
+        let needs = [
+            self.players[0].needs_replacement(),
+            self.players[1].needs_replacement(),
+        ];
+        if needs.contains(&true) {
+            self.prev_state.push(self.state);
+            self.state = GameState::AwaitingSwitch;
+            self.players.active = Some(if needs[0] { 0 } else { 1 });
+        }
+    }
+
+    /// sends out every picked replacement together, then runs entry hazards and switch in hooks
+    /// for each of them in speed order
+    fn exec_replacements(&mut self) {
+        let pending: Vec<usize> = (0..self.players.data.len())
+            .filter(|&player| self.players[player].needs_replacement())
+            .collect();
+        for &player in &pending {
+            self.players.active = Some(player);
+            let Some(&MoveSelection::Switch(idx)) = self.players[player].inputs.last() else {
+                panic!("replacement picked without a switch input");
+            };
+            write_log(&mut self.log, BattleEvent::SelectReplacement { player });
+            self.apply_effects(vec![Effect::Switch(idx)]);
+        }
+
+        self.order_turn_by_speed();
+        for _ in 0..self.players.data.len() {
+            if pending.contains(&self.players.idx(PlayerId::Active)) {
+                let effects = self.calc_switch(PlayerId::Active);
+                self.apply_effects(effects);
+                self.exec_switch_in();
+            }
+            self.invert_active_player();
+        }
+        // a replacement fainting to entry hazards needs replacing in turn
+        self.await_replacements();
+    }
+
+    /// runs the switch in hooks of the active player's mon, unless hazards already fainted it
+    fn exec_switch_in(&mut self) {
+        let mut effects = Vec::new();
+        if let Some(mon) = self.players[PlayerId::Active].roster.active() {
+            for trigger in mon.triggers() {
+                effects.extend(trigger.on_switch_in());
+            }
+        }
+        self.apply_effects(effects);
+    }
+
+    /// runs the move end hooks of both mons once the move's effects have all been applied
+    fn exec_move_end(&mut self) {
+        for target in [PlayerId::Active, PlayerId::Inactive] {
+            let mut effects = Vec::new();
+            if let Some(mon) = self.players[target].roster.active() {
+                for trigger in mon.triggers() {
+                    effects.extend(trigger.on_move_end(target, mon));
+                }
             }
+            self.apply_effects(effects);
         }
-        write_log(&mut self.log, String::new());
     }
 
-    /// if return Some move is skipped with message. moveskip statuses mutated
-    fn exec_moveskip(&mut self) -> Option<String> {
-        let mut rand = thread_rng();
-        let active_mon = &mut self.players[PlayerId::Active]
+    /// runs the update hooks of the target's mon after its hp, status or stages change
+    fn exec_update(&mut self, target: PlayerId) {
+        let mut effects = Vec::new();
+        if let Some(mon) = self.players[target].roster.active() {
+            for trigger in mon.triggers() {
+                effects.extend(trigger.on_update(target, mon));
+            }
+        }
+        self.apply_effects(effects);
+    }
+
+    /// rolls the statuses that can stop the active mon from moving, true if it loses its move.
+    /// Waking up, thawing and snapping out of confusion are logged here, as is a confusion
+    /// self-hit which also deals its damage
+    fn exec_moveskip(&mut self) -> bool {
+        let active_mon = self.players[PlayerId::Active]
             .roster
             .active()
             .expect("no active mon");
-        let mut removed_statuses = Vec::new();
-        let mut message = None;
-        let mut statusblock = active_mon.status.try_borrow_mut().unwrap();
-        for status in &[
-            Status::Paralyse,
-            Status::Sleep,
-            Status::Freeze,
-            Status::Confusion,
-            Status::Flinch,
-        ] {
-            if let Some(value) = statusblock.data.get_mut(status) {
+        let mon = active_mon.id;
+        // a frozen mon picking a fire move thaws itself out
+        let fire_move = self.players[PlayerId::Active]
+            .inputs
+            .last()
+            .and_then(|input| active_mon.get_move(input))
+            .is_some_and(|move_| move_.poke_type == Type::Fire);
+        let mut ended = Vec::new();
+        let mut skipped = None;
+        {
+            let mut statusblock = active_mon.status.borrow_mut();
+            for status in [
+                Status::Sleep,
+                Status::Freeze,
+                Status::Flinch,
+                Status::Confusion,
+                Status::Paralyse,
+            ] {
+                let Some(value) = statusblock.data.get_mut(&status) else {
+                    continue;
+                };
                 match status {
-                    Status::Paralyse => {
-                        if rand.gen_range(0..=3) == 0 {
-                            message = Some(format!("{} was full para", active_mon.id));
-                        }
-                    }
+                    Status::Sleep | Status::Confusion if *value == 0 => ended.push(status),
                     Status::Sleep => {
-                        if *value == 0 {
-                            write_log(&mut self.log, format!("{} woke up!", active_mon.id));
-                            removed_statuses.push(status);
-                        } else {
-                            *value -= 1;
-                            message = Some(format!("{} was sleeping", active_mon.id));
-                        }
-                    }
-                    Status::Freeze => {
-                        if rand.gen_range(0..=9) == 0 {
-                            write_log(&mut self.log, format!("{} thawed!", active_mon.id));
-                            removed_statuses.push(status);
-                        } else {
-                            message = Some(format!("{} is frozen", active_mon.id));
-                        }
+                        *value -= 1;
+                        skipped = Some(status);
                     }
                     Status::Confusion => {
-                        if *value == 0 {
-                            write_log(
-                                &mut self.log,
-                                format!("{} snapped out of confusion", active_mon.id),
-                            );
-                            removed_statuses.push(status);
-                        } else if rand.gen_range(0..=1) == 0 {
-                            message =
-                                Some(format!("{} hit itself in confusion(todo)", active_mon.id));
-                            *value -= 1;
-                        } else {
-                            *value -= 1;
+                        *value -= 1;
+                        if self.rng.gen_range(0..=1) == 0 {
+                            skipped = Some(status);
                         }
                     }
-                    Status::Flinch => {
-                        message = Some(format!("{} flinched", active_mon.id));
-                        removed_statuses.push(status);
+                    Status::Freeze if fire_move || self.rng.gen_range(0..=9) == 0 => {
+                        ended.push(status)
                     }
+                    Status::Freeze | Status::Flinch => skipped = Some(status),
+                    Status::Paralyse if self.rng.gen_range(0..=3) == 0 => skipped = Some(status),
                     _ => {}
                 }
+                if skipped.is_some() {
+                    break;
+                }
             }
-        }
-        if let Ok(mut data) = active_mon.status.try_borrow_mut() {
-            for status in removed_statuses {
-                data.data.remove(status);
+            for status in &ended {
+                statusblock.remove(status);
             }
         }
-        message
+
+        for status in ended {
+            write_log(&mut self.log, BattleEvent::StatusEnded { mon, status });
+        }
+        let Some(cause) = skipped else {
+            return false;
+        };
+        write_log(&mut self.log, BattleEvent::MoveSkipped { mon, cause });
+        if cause == Status::Confusion {
+            let effect = self.calc_confusion();
+            self.apply_effects(vec![effect]);
+        }
+        true
     }
 
-    fn calc_move(&self) -> Vec<Effect> {
+    /// the active mon hits itself with a typeless 40 power physical attack
+    fn calc_confusion(&mut self) -> Effect {
+        let active_mon = self.players[PlayerId::Active]
+            .roster
+            .active()
+            .expect("no active mon");
+        let calc = DamageCalc {
+            level: active_mon.spread.level,
+            base_power: 40,
+            attack: active_mon.stats[StatId::Atk].curr(),
+            defense: active_mon.stats[StatId::Def].curr(),
+            ..Default::default()
+        };
+        let damage = calc.calc(
+            self.rng
+                .gen_range(DamageCalc::MIN_ROLL..=DamageCalc::MAX_ROLL),
+        );
+        Effect::Damage(PlayerId::Active, Damage::Normal(damage))
+    }
+
+    fn calc_move(&mut self) -> Vec<Effect> {
         let active_player = &self.players[PlayerId::Active];
         let active_mon = &active_player
             .roster
             .active()
             .expect("move used with no active mon");
-        let selected_move =
-            if let MoveSelection::Move(idx) = active_player.inputs.last().expect("no inputs") {
-                &active_mon.moves[*idx]
-            } else {
-                panic!("move calculatd with switch input");
-            };
+        let selected_move = active_mon
+            .get_move(active_player.inputs.last().expect("no inputs"))
+            .expect("move calculatd with switch input");
 
         let mut out = Vec::new();
-        if let (Some(bp), Some(inactive_mon)) = (
+        // the target's triggers run before the accuracy roll and can block the move outright
+        if let (PlayerId::Inactive, Some(inactive_mon)) = (
+            selected_move.target,
+            self.players[PlayerId::Inactive].roster.active(),
+        ) {
+            let mut blocked = false;
+            for trigger in inactive_mon.triggers() {
+                let (effects, block) = trigger.on_before_hit(selected_move);
+                out.extend(effects);
+                blocked |= block;
+            }
+            if blocked {
+                return out;
+            }
+        }
+
+        // status moves that give the target a status fail before rolling to hit, and the few
+        // flagged with type immunity also respect the type chart, so Thunder Wave misses
+        // Ground types while Hypnosis still puts Dark types to sleep
+        if let (None, PlayerId::Inactive, Some(inactive_mon)) = (
             selected_move.base_power,
+            selected_move.target,
             self.players[PlayerId::Inactive].roster.active(),
         ) {
-            let (atk, def, burn, weather, random, stab, eff) = (
-                if selected_move.damage_type == Mtype::Physical {
-                    active_mon.stats[StatId::Atk].curr() as f32
+            for effect in &selected_move.effects {
+                let Effect::InflictStatus(PlayerId::Inactive, status) = *effect else {
+                    continue;
+                };
+                let immune = selected_move.type_immunity
+                    && inactive_mon
+                        .type_eff_split(&selected_move.poke_type)
+                        .contains(&0.0);
+                let event = if immune {
+                    let player = self.players.idx(PlayerId::Inactive);
+                    Some(BattleEvent::NoEffect {
+                        player,
+                        mon: inactive_mon.id,
+                    })
                 } else {
-                    active_mon.stats[StatId::Spa].curr() as f32
-                },
-                if selected_move.damage_type == Mtype::Physical {
-                    inactive_mon.stats[StatId::Def].curr() as f32
+                    self.status_immunity(PlayerId::Inactive, status)
+                };
+                if let Some(event) = event {
+                    write_log(&mut self.log, event);
+                    return out;
+                }
+            }
+        }
+
+        // moves without an accuracy never miss
+        if let Some(accuracy) = selected_move.accuracy {
+            let evasion = self.players[PlayerId::Inactive]
+                .roster
+                .active()
+                .map_or(0, |mon| mon.stats[StatId::Eva].stage());
+            let stage = (active_mon.stats[StatId::Acc].stage() - evasion).clamp(-6, 6);
+            let (num, den) = accuracy_fraction(stage);
+            if self.rng.gen::<f32>() >= accuracy * num as f32 / den as f32 {
+                let (player, mon) = (self.players.idx(PlayerId::Active), active_mon.id);
+                write_log(&mut self.log, BattleEvent::MoveMissed { player, mon });
+                return out;
+            }
+        }
+
+        if let (Some(bp), Some(inactive_mon)) = (
+            selected_move.base_power,
+            self.players[PlayerId::Inactive].roster.active(),
+        ) {
+            let effectiveness = inactive_mon.type_eff_split(&selected_move.poke_type);
+            if effectiveness.contains(&0.0) {
+                let (player, mon) = (self.players.idx(PlayerId::Inactive), inactive_mon.id);
+                write_log(&mut self.log, BattleEvent::NoEffect { player, mon });
+                return out;
+            }
+
+            let crit_stage = selected_move.crit_stage
+                + active_mon.item.borrow().map_or(0, |item| item.crit_stage());
+            let crit = self.rng.gen_range(0..crit_rate(crit_stage)) == 0;
+            let (atk_stat, def_stat) = if selected_move.damage_type == Mtype::Physical {
+                (
+                    &active_mon.stats[StatId::Atk],
+                    &inactive_mon.stats[StatId::Def],
+                )
+            } else {
+                (
+                    &active_mon.stats[StatId::Spa],
+                    &inactive_mon.stats[StatId::Spd],
+                )
+            };
+
+            let weather = match (*self.weather.borrow(), selected_move.poke_type) {
+                (Some(WeatherId::Rain), Type::Water) => Modifier(3, 2),
+                (Some(WeatherId::Rain), Type::Fire) => Modifier(1, 2),
+                (Some(WeatherId::Sun), Type::Fire) => Modifier(3, 2),
+                (Some(WeatherId::Sun), Type::Water) => Modifier(1, 2),
+                _ => Modifier::NEUTRAL,
+            };
+            let mut calc = DamageCalc {
+                level: active_mon.spread.level,
+                base_power: bp,
+                // crits ignore the attacker's drops and the defender's boosts
+                attack: if crit {
+                    atk_stat.with_stage(atk_stat.stage().max(0))
                 } else {
-                    inactive_mon.stats[StatId::Spd].curr() as f32
+                    atk_stat.curr()
                 },
-                if active_mon.status.borrow().data.contains_key(&Status::Burn) {
-                    0.5f32
+                defense: if crit {
+                    def_stat.with_stage(def_stat.stage().min(0))
                 } else {
-                    1.0f32
+                    def_stat.curr()
                 },
-                1.0f32,
-                thread_rng().gen_range(85..=100) as f32 / 100.0,
-                if active_mon
+                burn: selected_move.damage_type == Mtype::Physical
+                    && active_mon.status.borrow().data.contains_key(&Status::Burn),
+                weather,
+                flash_fire: selected_move.poke_type == Type::Fire
+                    && active_mon
+                        .status
+                        .borrow()
+                        .data
+                        .contains_key(&Status::FlashFire),
+                crit,
+                stab: active_mon
                     .poketype
                     .borrow()
-                    .contains(selected_move.poke_type)
-                {
-                    1.5f32
-                } else {
-                    1.0f32
-                },
-                selected_move
-                    .poke_type
-                    .calc_eff(&inactive_mon.poketype.borrow()),
+                    .contains(selected_move.poke_type),
+                effectiveness,
+                ..Default::default()
+            };
+            let mut after_attack = Vec::new();
+            for trigger in active_mon.triggers() {
+                after_attack.extend(trigger.on_attack(selected_move, &mut calc));
+            }
+            for trigger in inactive_mon.triggers() {
+                out.extend(trigger.on_defend(selected_move, &mut calc));
+            }
+
+            let damage = calc.calc(
+                self.rng
+                    .gen_range(DamageCalc::MIN_ROLL..=DamageCalc::MAX_ROLL),
             );
-            out.push(Effect::Damage(
-                PlayerId::Inactive,
-                Damage::Normal(
-                    (((42.0f32 * bp as f32 * (atk / def)) / 50.0f32 * burn * weather)
-                        * stab
-                        * eff
-                        * random) as i32,
-                ),
-            ))
+            if crit && damage > 0 {
+                write_log(&mut self.log, BattleEvent::CriticalHit);
+            }
+            out.push(Effect::Damage(PlayerId::Inactive, Damage::Normal(damage)));
+            // fire moves thaw the target out
+            if selected_move.poke_type == Type::Fire {
+                out.push(Effect::EndStatus(PlayerId::Inactive, Status::Freeze));
+            }
+            for trigger in inactive_mon.triggers() {
+                out.extend(trigger.on_after_hit(selected_move, &mut self.rng));
+            }
+            out.extend(after_attack);
         }
         match selected_move.freq {
             Some(data) => {
-                if thread_rng().gen::<f32>() > data {
+                let chance = active_mon
+                    .triggers()
+                    .iter()
+                    .fold(data, |chance, trigger| trigger.modify_chance(chance));
+                if self.rng.gen::<f32>() < chance {
                     out.append(&mut selected_move.effects.clone());
                 }
             }
@@ -487,11 +765,54 @@ impl Game {
         out
     }
 
+    /// if the target's mon can't be given `status`, the event logged when a move trying to give
+    /// it fails. Secondary effects, items and hazards that hit an immunity fail silently
+    fn status_immunity(&self, target: PlayerId, status: Status) -> Option<BattleEvent> {
+        let mon = self.players[target].roster.active()?;
+        let (player, id) = (self.players.idx(target), mon.id);
+        let statusblock = mon.status.borrow();
+        // drowsiness ends in sleep, so it needs the same free status slot
+        let nv = status.is_nv() || status == Status::Drowsy;
+        if statusblock.data.contains_key(&status) || nv && statusblock.nv.is_some() {
+            return Some(BattleEvent::MoveFailed);
+        }
+
+        let poketype = mon.poketype.borrow();
+        let type_immune = match status {
+            Status::Burn => poketype.contains(Type::Fire),
+            Status::Poison | Status::Toxic => {
+                poketype.contains(Type::Poison) || poketype.contains(Type::Steel)
+            }
+            Status::Freeze => poketype.contains(Type::Ice),
+            _ => false,
+        };
+        let blocked = mon.triggers().iter().any(|trigger| {
+            trigger.blocks_status(status)
+                || status == Status::Drowsy && trigger.blocks_status(Status::Sleep)
+        });
+        if type_immune || blocked {
+            return Some(BattleEvent::NoEffect { player, mon: id });
+        }
+
+        if status == Status::Freeze && *self.weather.borrow() == Some(WeatherId::Sun) {
+            return Some(BattleEvent::MoveFailed);
+        }
+        None
+    }
+
     fn calc_switch(&self, target: PlayerId) -> Vec<Effect> {
         let mut out = Vec::new();
         if self.players[target].hazards.stealth_rock.data == 1 {
             out.push(Effect::Damage(target, Damage::Fractional(1, 8)))
         }
+        // spikes and toxic spikes only touch grounded mons
+        let grounded = self.players[target]
+            .roster
+            .active()
+            .is_some_and(|mon| mon.is_grounded());
+        if !grounded {
+            return out;
+        }
         match self.players[target].hazards.toxic_spikes.data {
             1 => out.push(Effect::InflictStatus(target, Status::Poison)),
             2 => out.push(Effect::InflictStatus(target, Status::Toxic)),
@@ -535,7 +856,7 @@ impl Game {
 
             let faster = (p1_mon.stats[StatId::Spe].curr() > p2_mon.stats[StatId::Spe].curr())
                 | (p1_mon.stats[StatId::Spe].curr() == p2_mon.stats[StatId::Spe].curr()
-                    && thread_rng().gen::<bool>());
+                    && self.rng.gen::<bool>());
 
             let (priority1, priority2) = (
                 match move1 {
@@ -557,12 +878,12 @@ impl Game {
 
     fn order_turn_by_speed(&mut self) {
         self.players.active = if let (Some(p1_mon), Some(p2_mon)) = (
-            self.players[PlayerId::Active].roster.active(),
-            self.players[PlayerId::Inactive].roster.active(),
+            self.players[0].roster.active(),
+            self.players[1].roster.active(),
         ) {
             if (p1_mon.stats[StatId::Spe].curr() > p2_mon.stats[StatId::Spe].curr())
                 | ((p1_mon.stats[StatId::Spe].curr() == p2_mon.stats[StatId::Spe].curr())
-                    && thread_rng().gen())
+                    && self.rng.gen())
             {
                 Some(0)
             } else {
@@ -582,136 +903,314 @@ impl Game {
         for effect in effects {
             match effect {
                 Effect::InflictStatus(target, status) => {
+                    if self.status_immunity(target, status).is_some() {
+                        continue;
+                    }
+                    let player = self.players.idx(target);
                     if let Some(mon) = self.players[target].roster.active_mut() {
                         let mut success = false;
                         if let Ok(mut status_ref) = mon.status.try_borrow_mut() {
-                            success = status_ref.add(&status);
+                            success = status_ref.add(&status, &mut self.rng);
                         }
 
                         if success {
-                            let mon_name = mon.id.to_string();
                             write_log(
                                 &mut self.log,
-                                format!("{}'s {} was {}", self.players[target], mon_name, status),
+                                BattleEvent::StatusInflicted {
+                                    player,
+                                    mon: mon.id,
+                                    status,
+                                },
                             );
+                            self.exec_update(target);
                         }
                     }
                 }
 
                 Effect::AlterStat(target, stat, stat_mod) => {
+                    let player = self.players.idx(target);
                     if let Some(target_mon) = self.players[target].roster.active_mut() {
-                        let mod_str = if stat_mod > 0 { "raised" } else { "lowered" };
-                        if target_mon.stats[stat].alter(stat_mod) {
-                            let mon_name = target_mon.id.to_string();
-                            write_log(
-                                &mut self.log,
-                                format!(
-                                    "{}'s {} {} was {}",
-                                    self.players[target], mon_name, stat, mod_str,
-                                ),
-                            );
-                        }
+                        // near the limits only part of the change applies, log what did
+                        let prev = target_mon.stats[stat].stage();
+                        target_mon.stats[stat].alter(stat_mod);
+                        let (mon, stages) = (target_mon.id, target_mon.stats[stat].stage() - prev);
+                        write_log(
+                            &mut self.log,
+                            if stages == 0 {
+                                BattleEvent::StatUnchanged {
+                                    player,
+                                    mon,
+                                    stat,
+                                    raise: stat_mod > 0,
+                                }
+                            } else {
+                                BattleEvent::StatChanged {
+                                    player,
+                                    mon,
+                                    stat,
+                                    stages,
+                                }
+                            },
+                        );
+                        self.exec_update(target);
                     }
                 }
 
                 Effect::InflictHazard(target, hazard) => {
+                    let player = self.players.idx(target);
                     let target_player = &mut self.players[target];
                     if !target_player.hazards[hazard].is_max() {
                         target_player.hazards[hazard] += 1;
-                        let player_name = target_player.to_string();
-                        let hazard_name = target_player.hazards[hazard].to_string();
-                        write_log(
-                            &mut self.log,
-                            format!("{} was placed on {}'s field", hazard_name, player_name),
-                        );
+                        write_log(&mut self.log, BattleEvent::HazardSet { player, hazard });
                     }
                 }
 
                 Effect::ClearHazard(target) => {
-                    let target_player = &mut self.players[target];
-                    target_player.hazards = HazardBlock::default();
-                    let player_name = target_player.to_string();
-                    write_log(
-                        &mut self.log,
-                        format!("hazards were cleared from {}'s field", player_name),
-                    );
+                    let player = self.players.idx(target);
+                    self.players[target].hazards = HazardBlock::default();
+                    write_log(&mut self.log, BattleEvent::HazardsCleared { player });
                 }
 
                 Effect::Damage(target, damage) => {
+                    let player = self.players.idx(target);
                     if let Some(target_mon) = self.players[target].roster.active_mut() {
+                        let mut amount = damage.collapse(target_mon.hp);
+                        let mut triggered = Vec::new();
+                        // only attacks are normal damage, residual damage skips these triggers
+                        if let Damage::Normal(_) = damage {
+                            for trigger in target_mon.triggers() {
+                                let (taken, effects) =
+                                    trigger.on_damage_taken(target, target_mon, amount);
+                                amount = taken;
+                                triggered.extend(effects);
+                            }
+                        }
+
                         let prev_hp = target_mon.hp.data;
-                        target_mon.hp -= damage.collapse(target_mon.hp);
-                        let diff = prev_hp - target_mon.hp.data;
-                        let mon_name = target_mon.id.to_string();
-                        let rem_hp = target_mon.hp.data;
+                        target_mon.hp -= amount;
+                        let mon = target_mon.id;
 
                         write_log(
                             &mut self.log,
-                            format!("{}'s {} lost {} hp", self.players[target], mon_name, diff),
+                            BattleEvent::Damage {
+                                player,
+                                mon,
+                                amount: prev_hp - target_mon.hp.data,
+                            },
                         );
 
-                        if rem_hp == 0 {
+                        if target_mon.hp.data == 0 {
                             self.players[target].roster.kill();
-                            write_log(&mut self.log, String::from("They fainted"));
+                            write_log(&mut self.log, BattleEvent::Faint { player, mon });
+                        }
+                        self.apply_effects(triggered);
+                        self.exec_update(target);
+                    }
+                }
+
+                Effect::EndStatus(target, status) => {
+                    if let Some(target_mon) = self.players[target].roster.active() {
+                        let mut status_ref = target_mon.status.borrow_mut();
+                        if status_ref.data.contains_key(&status) {
+                            status_ref.remove(&status);
+                            write_log(
+                                &mut self.log,
+                                BattleEvent::StatusEnded {
+                                    mon: target_mon.id,
+                                    status,
+                                },
+                            );
                         }
                     }
                 }
 
                 Effect::Cure(target) => {
+                    let player = self.players.idx(target);
                     if let Some(target_mon) = self.players[target].roster.active_mut() {
+                        let mut success = false;
                         if let Ok(mut status_ref) = target_mon.status.try_borrow_mut() {
-                            status_ref.clear_nv();
+                            success = status_ref.clear_nv();
+                        }
+                        if success {
+                            write_log(
+                                &mut self.log,
+                                BattleEvent::StatusCured {
+                                    player,
+                                    mon: target_mon.id,
+                                },
+                            );
                         }
-                        let mon_name = target_mon.id.to_string();
+                    }
+                }
+
+                Effect::ShowAbility(target) => {
+                    let player = self.players.idx(target);
+                    if let Some(mon) = self.players[target].roster.active() {
                         write_log(
                             &mut self.log,
-                            format!(
-                                "{}'s {} was cured of status",
-                                self.players[target], mon_name
-                            ),
+                            BattleEvent::AbilityActivated {
+                                player,
+                                mon: mon.id,
+                                ability: mon.ability,
+                            },
                         );
                     }
                 }
 
+                Effect::ShowItem(target) => {
+                    let player = self.players.idx(target);
+                    if let Some(mon) = self.players[target].roster.active() {
+                        if let Some(item) = *mon.item.borrow() {
+                            write_log(
+                                &mut self.log,
+                                BattleEvent::ItemActivated {
+                                    player,
+                                    mon: mon.id,
+                                    item,
+                                },
+                            );
+                        }
+                    }
+                }
+
+                Effect::ConsumeItem(target) => {
+                    let player = self.players.idx(target);
+                    if let Some(mon) = self.players[target].roster.active_mut() {
+                        let item = mon.item.borrow_mut().take();
+                        if let Some(item) = item {
+                            mon.consumed = Some(item);
+                            write_log(
+                                &mut self.log,
+                                BattleEvent::ItemActivated {
+                                    player,
+                                    mon: mon.id,
+                                    item,
+                                },
+                            );
+                        }
+                    }
+                }
+
+                Effect::RemoveItem(target) => {
+                    let player = self.players.idx(target);
+                    if let Some(mon) = self.players[target].roster.active_mut() {
+                        let item = mon.item.borrow_mut().take();
+                        if let Some(item) = item {
+                            mon.item_knocked_off = true;
+                            write_log(
+                                &mut self.log,
+                                BattleEvent::ItemRemoved {
+                                    player,
+                                    mon: mon.id,
+                                    item,
+                                },
+                            );
+                        }
+                    }
+                }
+
+                Effect::SwapItems => {
+                    let player = self.players.idx(PlayerId::Active);
+                    let input = self.players[PlayerId::Active].inputs.last().copied();
+                    let mut events = Vec::new();
+                    if let (Some(user), Some(target)) = self.active_mons_mut() {
+                        if user.item.borrow().is_none() && target.item.borrow().is_none()
+                            || user.item_knocked_off
+                            || target.item_knocked_off
+                        {
+                            events.push(BattleEvent::MoveFailed);
+                        } else {
+                            user.item.swap(&target.item);
+                            // a choice item received mid move locks the user into that move
+                            target.moves.active = None;
+                            user.moves.active = match input {
+                                Some(MoveSelection::Move(idx)) => Some(idx),
+                                _ => None,
+                            };
+
+                            events.push(BattleEvent::ItemsSwapped {
+                                player,
+                                mon: user.id,
+                            });
+                            for (player, mon) in [(player, &*user), ((player + 1) % 2, &*target)] {
+                                if let Some(item) = *mon.item.borrow() {
+                                    events.push(BattleEvent::ItemObtained {
+                                        player,
+                                        mon: mon.id,
+                                        item,
+                                    });
+                                }
+                            }
+                        }
+                    }
+                    for event in events {
+                        write_log(&mut self.log, event);
+                    }
+                }
+
+                Effect::StealItem => {
+                    let player = self.players.idx(PlayerId::Active);
+                    let mut stolen = None;
+                    if let (Some(user), Some(target)) = self.active_mons_mut() {
+                        if user.item.borrow().is_none() && !user.item_knocked_off {
+                            let item = target.item.borrow_mut().take();
+                            if let Some(item) = item {
+                                *user.item.borrow_mut() = Some(item);
+                                user.moves.active = None;
+                                stolen = Some((user.id, item));
+                            }
+                        }
+                    }
+                    if let Some((mon, item)) = stolen {
+                        write_log(&mut self.log, BattleEvent::ItemStolen { player, mon, item });
+                    }
+                }
+
+                Effect::DrainPP(target, amount) => {
+                    let target_player = &mut self.players[target];
+                    if let (Some(&MoveSelection::Move(idx)), Some(target_mon)) = (
+                        target_player.inputs.last(),
+                        target_player.roster.active_mut(),
+                    ) {
+                        target_mon.moves[idx].pp -= amount;
+                    }
+                }
+
                 Effect::Heal(target, frac) => {
+                    let player = self.players.idx(target);
                     if let Some(target_mon) = self.players[target].roster.active_mut() {
                         if target_mon.hp.data > 0 {
                             let prev_hp = target_mon.hp.data;
                             target_mon.hp += target_mon.hp.max / frac;
-                            let diff = target_mon.hp.data - prev_hp;
-                            let mon_name = target_mon.id.to_string();
                             write_log(
                                 &mut self.log,
-                                format!(
-                                    "{}'s {} gained {} hp",
-                                    self.players[target], mon_name, diff
-                                ),
+                                BattleEvent::Heal {
+                                    player,
+                                    mon: target_mon.id,
+                                    amount: target_mon.hp.data - prev_hp,
+                                },
                             );
                         }
                     }
                 }
 
                 Effect::OHKO(target) => {
+                    let player = self.players.idx(target);
                     if let Some(target_mon) = self.players[target].roster.active_mut() {
                         target_mon.hp.data = 0;
-                        let mon_name = target_mon.id.to_string();
+                        let mon = target_mon.id;
                         self.players[target].roster.kill();
-                        write_log(
-                            &mut self.log,
-                            format!("{}'s {} fainted!", self.players[target], mon_name),
-                        );
+                        write_log(&mut self.log, BattleEvent::Faint { player, mon });
                     }
                 }
 
                 Effect::MidSwitch(target) => {
-                    let target_player = &mut self.players[target];
-                    if target_player.roster.dead != 1 {
+                    let player = self.players.idx(target);
+                    // a user that fainted to recoil is replaced at the end of the turn instead
+                    if self.players[target].has_active() && self.players[target].roster.dead != 1 {
                         self.prev_state.push(self.state);
                         self.state = GameState::AwaitingSwitch;
-                        write_log(
-                            &mut self.log,
-                            format!("{} selects a pokemon to switch to", self.players[target]),
-                        );
+                        write_log(&mut self.log, BattleEvent::SwitchPrompt { player });
                     }
                 }
 
@@ -719,52 +1218,74 @@ impl Game {
                     if *self.weather.borrow() != Some(weather) {
                         if let Ok(mut weather_ref) = self.weather.try_borrow_mut() {
                             *weather_ref = Some(weather);
-                            let weather_message = match weather {
-                                WeatherId::Sand => "A sandstorm kicked up!",
-                                WeatherId::Hail => "Hail starts",
-                                WeatherId::Rain => "Rain starts",
-                            };
-                            write_log(&mut self.log, weather_message.to_string());
+                            write_log(&mut self.log, BattleEvent::WeatherSet(weather));
                         }
                     }
                 }
 
                 Effect::Switch(idx) => {
+                    let player = self.players.idx(PlayerId::Active);
+                    if let Some(prev_mon) = self.players[PlayerId::Active].roster.active_mut() {
+                        prev_mon.switch_out();
+                    }
                     self.players[PlayerId::Active].roster.active = Some(idx);
-                    let active_player = &self.players[PlayerId::Active];
-                    let mon_name = &active_player.roster[idx].id;
-                    write_log(
-                        &mut self.log,
-                        format!("{} sends out {}", active_player, mon_name),
-                    );
+                    let mon = self.players[PlayerId::Active].roster[idx].id;
+                    write_log(&mut self.log, BattleEvent::Switch { player, mon });
                 }
             }
         }
     }
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
 pub enum Damage {
     Normal(i32),
     // fractional damage represented as a fraction, value 1 over value 2
     Fractional(i32, i32),
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
 pub enum Effect {
     AlterStat(PlayerId, StatId, i32),
     ClearHazard(PlayerId),
     Cure(PlayerId),
     Damage(PlayerId, Damage),
+    /// extra pp spent on the target's last used move
+    DrainPP(PlayerId, i32),
+    /// logs the target's ability activating
+    ShowAbility(PlayerId),
+    /// logs the target's held item activating
+    ShowItem(PlayerId),
+    /// the target's held item is used up
+    ConsumeItem(PlayerId),
+    /// knocks off the target's held item for the rest of the battle
+    RemoveItem(PlayerId),
+    /// trades the active and inactive mons' held items
+    SwapItems,
+    /// the active mon takes the inactive mon's held item if it isn't holding one
+    StealItem,
     Heal(PlayerId, i32),
     InflictHazard(PlayerId, HazardId),
     InflictStatus(PlayerId, Status),
+    /// the target's status wears off, like a frozen mon thawing
+    EndStatus(PlayerId, Status),
     MidSwitch(PlayerId),
     OHKO(PlayerId),
     SetWeather(WeatherId),
     Switch(usize),
 }
 
+/// one in this many hits is critical at the given crit stage
+fn crit_rate(stage: i8) -> u32 {
+    match stage {
+        i8::MIN..=0 => 16,
+        1 => 8,
+        2 => 4,
+        3 => 3,
+        _ => 2,
+    }
+}
+
 impl Damage {
     pub fn collapse(&self, value: BoundedI32) -> i32 {
         match self {
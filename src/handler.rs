@@ -6,6 +6,7 @@ use ratatui::crossterm::event::{KeyCode, KeyEvent};
 pub fn handle_key_events(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
     match key_event.code {
         KeyCode::Esc | KeyCode::Char('q') => {
+            app.save()?;
             app.quit();
         }
         KeyCode::Up => match app.ui.mode {
@@ -57,11 +58,7 @@ pub fn handle_key_events(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
             }
         },
         KeyCode::Char('u') => app.ui.log_idx += 1,
-        KeyCode::Char('d') => {
-            if app.ui.log_idx > 0 {
-                app.ui.log_idx -= 1
-            }
-        }
+        KeyCode::Char('d') if app.ui.log_idx > 0 => app.ui.log_idx -= 1,
         KeyCode::Enter => match app.ui.mode {
             IMode::PokeList => {
                 let _move = MoveSelection::Switch(app.ui.pokelist[0].selected().unwrap_or(7));
@@ -70,19 +67,23 @@ pub fn handle_key_events(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
                     .contains(&_move)
                 {
                     app.games.players[0].inputs.push(_move);
-                    app.games.execute_turn();
+                    app.execute_turn()?;
                 }
             }
             IMode::MoveList => {
-                let _move = MoveSelection::Move(app.ui.movelist[0].selected().unwrap_or(7));
-                if app.games.players[0]
-                    .list_valid_inputs(&app.games.state)
-                    .contains(&_move)
+                let valid = app.games.players[0].list_valid_inputs(&app.games.state);
+                // once every move is out of pp any selection struggles
+                let _move = if valid.contains(&MoveSelection::Struggle) {
+                    MoveSelection::Struggle
+                } else {
+                    MoveSelection::Move(app.ui.movelist[0].selected().unwrap_or(7))
+                };
+                if valid.contains(&_move)
                     && app.ui.pokelist[0].selected().unwrap_or(7)
                         == app.games.players[0].roster.active.unwrap_or(8)
                 {
                     app.games.players[0].inputs.push(_move);
-                    app.games.execute_turn();
+                    app.execute_turn()?;
                 }
             }
             _ => {}
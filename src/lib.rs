@@ -4,16 +4,23 @@ use std::error::Error;
 type EmptyResult = Result<(), Box<dyn Error>>;
 
 pub mod app;
+pub mod battle_event;
 pub mod bounded_i32;
+pub mod damage;
+pub mod dex;
 pub mod event;
 pub mod game;
 pub mod handler;
 pub mod moves;
+pub mod paste;
 pub mod player;
 pub mod pokemon;
 pub mod poketype;
 pub mod preset;
+pub mod replay;
+pub mod save;
 pub mod selvec;
+pub mod sim;
 pub mod stat;
 pub mod status;
 pub mod trigger;
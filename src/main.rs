@@ -1,15 +1,101 @@
-use pkml::app::{App, AppResult};
+use pkml::app::{App, AppResult, SAVE_PATH};
+use pkml::dex;
 use pkml::event::{Event, EventHandler};
 use pkml::handler::handle_key_events;
+use pkml::paste::{export_team, parse_team};
+use pkml::player::default_team;
+use pkml::replay::Replay;
+use pkml::sim::simulate;
 use pkml::tui::Tui;
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
-use std::io;
+use std::{env, fs, io};
 
 fn main() -> AppResult<()> {
-    // Create an application.
-    let mut app = App::new();
+    let args: Vec<String> = env::args().skip(1).collect();
+    // Load the species and move data up front so bad files are reported, not panicked on.
+    dex::init()?;
+    match args.first().map(String::as_str) {
+        Some("sim") => run_sim(&args[1..]),
+        Some("replay") => run_replay(&args[1..]),
+        Some("play") => run_play(&args[1..]),
+        Some("export") => run_export(),
+        Some(other) => Err(format!("unknown subcommand: {}", other).into()),
+        None => run_tui(resume()?),
+    }
+}
+
+/// picks up the saved battle, a save that can't be read is moved aside rather than left for
+/// the new battle to overwrite
+fn resume() -> AppResult<App> {
+    match App::resume() {
+        Ok(app) => Ok(app),
+        Err(err) => {
+            let backup = format!("{}.bak", SAVE_PATH);
+            fs::rename(SAVE_PATH, &backup)?;
+            eprintln!(
+                "could not resume saved battle, moved it to {}: {}",
+                backup, err
+            );
+            Ok(App::new())
+        }
+    }
+}
+
+/// `pkml play <team file> [bot team file]` starts a new battle with Showdown pastes, the bot
+/// keeps the default team if it isn't given one
+fn run_play(args: &[String]) -> AppResult<()> {
+    let path = args
+        .first()
+        .ok_or("usage: pkml play <team file> [bot team file]")?;
+    let team =
+        parse_team(&fs::read_to_string(path)?).map_err(|err| format!("{}: {}", path, err))?;
+    let bot = match args.get(1) {
+        Some(path) => {
+            parse_team(&fs::read_to_string(path)?).map_err(|err| format!("{}: {}", path, err))?
+        }
+        None => default_team(),
+    };
+    run_tui(App::with_teams([team, bot]))
+}
+
+/// `pkml export` prints the default team as a Showdown paste
+fn run_export() -> AppResult<()> {
+    print!("{}", export_team(&default_team()));
+    Ok(())
+}
+
+/// `pkml sim [games] [seed]` plays bot vs bot games without a terminal
+fn run_sim(args: &[String]) -> AppResult<()> {
+    let games = match args.first() {
+        Some(data) => data.parse()?,
+        None => 1000,
+    };
+    let seed = match args.get(1) {
+        Some(data) => data.parse()?,
+        None => 0,
+    };
 
+    print!("{}", simulate(games, seed));
+    Ok(())
+}
+
+/// `pkml replay <file>` re-simulates a recorded battle and prints its log
+fn run_replay(args: &[String]) -> AppResult<()> {
+    let path = args.first().ok_or("usage: pkml replay <file>")?;
+    let game = Replay::load(path)?.play()?;
+
+    for (turn, events) in game.log.iter().enumerate() {
+        println!("== Turn {} ==", turn);
+        for event in events {
+            println!("{}", event.render(&game.players.data));
+        }
+    }
+    println!("{}", game.state);
+    Ok(())
+}
+
+fn run_tui(mut app: App) -> AppResult<()> {
     // Initialize the terminal user interface.
     let backend = CrosstermBackend::new(io::stderr());
     let terminal = Terminal::new(backend)?;
@@ -17,20 +103,25 @@ fn main() -> AppResult<()> {
     let mut tui = Tui::new(terminal, events);
     tui.init()?;
 
-    // Start the main loop.
+    let result = run_loop(&mut tui, &mut app);
+
+    // Exit the user interface, also when the loop failed, e.g. on a save that couldn't be
+    // written, so the terminal isn't left in raw mode.
+    let exit = tui.exit();
+    result.and(exit)
+}
+
+fn run_loop(tui: &mut Tui<CrosstermBackend<io::Stderr>>, app: &mut App) -> AppResult<()> {
     while app.running {
         // Render the user interface.
-        tui.draw(&mut app)?;
+        tui.draw(app)?;
         // Handle events.
         match tui.events.next()? {
             Event::Tick => app.tick(),
-            Event::Key(key_event) => handle_key_events(key_event, &mut app)?,
+            Event::Key(key_event) => handle_key_events(key_event, app)?,
             Event::Mouse(_) => {}
             Event::Resize(_, _) => {}
         }
     }
-
-    // Exit the user interface.
-    tui.exit()?;
     Ok(())
 }
@@ -0,0 +1,188 @@
+//! Teams in the Pokémon Showdown export format:
+//!
+//! ```text
+//! Jirachi @ Leftovers
+//! Ability: Serene Grace
+//! EVs: 252 HP / 52 Atk / 96 SpD / 108 Spe
+//! Careful Nature
+//! - Iron Head
+//! - Body Slam
+//! ```
+//!
+//! Sets are separated by blank lines. Nicknames, genders and lines we have no use for (Shiny,
+//! Happiness, Tera Type, ...) are accepted and dropped.
+
+use std::fmt::{self, Display};
+
+use crate::dex::dex;
+use crate::preset::{PokeId, Set};
+use crate::stat::{Nature, Spread};
+use crate::trigger::{Ability, Item};
+
+/// names Showdown uses for the six stats, in spread order
+const STAT_NAMES: [&str; 6] = ["HP", "Atk", "Def", "SpA", "SpD", "Spe"];
+
+/// most mons a team can have
+pub const TEAM_SIZE: usize = 6;
+
+/// reads a whole team, one to six sets
+pub fn parse_team(text: &str) -> Result<Vec<Set>, String> {
+    let mut team = Vec::new();
+    let mut block: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim).chain([""]) {
+        // "=== [gen4ou] Team Name ===" headers from a teambuilder export are skipped
+        if line.is_empty() || line.starts_with("===") {
+            if !block.is_empty() {
+                team.push(Set::from_paste(&block.join("\n"))?);
+                block.clear();
+            }
+        } else {
+            block.push(line);
+        }
+    }
+
+    if team.is_empty() || team.len() > TEAM_SIZE {
+        return Err(format!(
+            "team has {} sets, expected 1 to {}",
+            team.len(),
+            TEAM_SIZE
+        ));
+    }
+    Ok(team)
+}
+
+/// writes a team the way Showdown exports it
+pub fn export_team(team: &[Set]) -> String {
+    team.iter()
+        .map(Set::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl Set {
+    /// reads a single set, anything left out falls back to the species' preset ability, no
+    /// item, a neutral nature, no evs and 31 ivs
+    pub fn from_paste(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+        let header = lines.next().ok_or("empty set")?;
+        let (name, item) = match header.split_once('@') {
+            Some((name, item)) => (name.trim(), Some(item.trim())),
+            None => (header, None),
+        };
+        let species = PokeId::from_name(parse_species(name))
+            .ok_or_else(|| format!("unknown species {}", parse_species(name)))?;
+        let item = item
+            .map(|name| Item::from_name(name).ok_or_else(|| format!("unknown item {}", name)))
+            .transpose()?;
+
+        let mut ability = None;
+        let mut spread = Spread::default();
+        let mut moves = Vec::new();
+        for line in lines {
+            if let Some(name) = line.strip_prefix('-') {
+                moves.push(name.trim().to_string());
+            } else if let Some(name) = line.strip_prefix("Ability:") {
+                let name = name.trim();
+                ability = Some(
+                    Ability::from_name(name).ok_or_else(|| format!("unknown ability {}", name))?,
+                );
+            } else if let Some(level) = line.strip_prefix("Level:") {
+                spread.level = level
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("bad level {}", level.trim()))?;
+            } else if let Some(evs) = line.strip_prefix("EVs:") {
+                parse_stats(evs, &mut spread.evs)?;
+            } else if let Some(ivs) = line.strip_prefix("IVs:") {
+                parse_stats(ivs, &mut spread.ivs)?;
+            } else if let Some(name) = line.strip_suffix("Nature") {
+                let name = name.trim();
+                spread.nature =
+                    Nature::from_name(name).ok_or_else(|| format!("unknown nature {}", name))?;
+            }
+        }
+
+        let name = dex().species(species).name.as_str();
+        spread
+            .validate()
+            .map_err(|err| format!("{}: {}", name, err))?;
+        Ok(Self {
+            species,
+            ability: ability.unwrap_or(dex().species(species).preset.ability),
+            item,
+            moves: dex()
+                .resolve_moves(&moves)
+                .map_err(|err| format!("{}: {}", name, err))?,
+            spread,
+        })
+    }
+}
+
+/// "Nickname (Species) (M)" down to the species
+fn parse_species(name: &str) -> &str {
+    let mut name = name.trim();
+    for gender in ["(M)", "(F)"] {
+        name = name.strip_suffix(gender).unwrap_or(name).trim_end();
+    }
+    match name
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once('('))
+    {
+        Some((_, species)) => species.trim(),
+        None => name,
+    }
+}
+
+/// "252 HP / 4 Def" into the matching slots of `out`, slots not mentioned keep their value
+fn parse_stats(text: &str, out: &mut [i32; 6]) -> Result<(), String> {
+    for part in text.split('/') {
+        let (value, stat) = part
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| format!("bad stat {}", part.trim()))?;
+        let idx = STAT_NAMES
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(stat.trim()))
+            .ok_or_else(|| format!("unknown stat {}", stat.trim()))?;
+        out[idx] = value
+            .parse()
+            .map_err(|_| format!("bad value {}", part.trim()))?;
+    }
+    Ok(())
+}
+
+/// "252 HP / 4 Def" for the slots that differ from `default`
+fn write_stats(values: &[i32; 6], default: i32) -> Option<String> {
+    let parts: Vec<String> = values
+        .iter()
+        .zip(STAT_NAMES)
+        .filter(|(value, _)| **value != default)
+        .map(|(value, name)| format!("{} {}", value, name))
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(" / "))
+}
+
+impl Display for Set {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.species)?;
+        if let Some(item) = self.item {
+            write!(f, " @ {}", item)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "Ability: {}", self.ability)?;
+        if self.spread.level != 100 {
+            writeln!(f, "Level: {}", self.spread.level)?;
+        }
+        if let Some(evs) = write_stats(&self.spread.evs, 0) {
+            writeln!(f, "EVs: {}", evs)?;
+        }
+        writeln!(f, "{} Nature", self.spread.nature)?;
+        if let Some(ivs) = write_stats(&self.spread.ivs, 31) {
+            writeln!(f, "IVs: {}", ivs)?;
+        }
+        for id in &self.moves {
+            writeln!(f, "- {}", id)?;
+        }
+        Ok(())
+    }
+}
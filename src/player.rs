@@ -3,13 +3,34 @@ use std::fmt::Display;
 use std::ops::{Index, IndexMut};
 use std::rc::Rc;
 
-
 use crate::bounded_i32::BoundedI32;
+use crate::dex::dex;
 use crate::game::{GameState, HazardId, MoveSelection, WeatherId};
 use crate::pokemon::Pokemon;
-use crate::preset::PokeId;
+use crate::preset::{PokeId, Set};
 use crate::selvec::PointerVec;
 
+/// lineup used when no team is given
+pub const DEFAULT_TEAM: [&str; 6] = [
+    "Jirachi",
+    "Tyranitar",
+    "Heatran",
+    "Breloom",
+    "Zapdos",
+    "Starmie",
+];
+
+/// the preset sets of [`DEFAULT_TEAM`]
+pub fn default_team() -> Vec<Set> {
+    DEFAULT_TEAM
+        .iter()
+        .map(|name| {
+            let id = PokeId::from_name(name).expect("default team species missing from data");
+            dex().species(id).preset.clone()
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Player {
     pub name: &'static str,
@@ -17,6 +38,8 @@ pub struct Player {
     pub hazards: HazardBlock,
     pub roster: PointerVec<Pokemon>,
     pub inputs: Vec<MoveSelection>,
+    /// lineup the player started with, the roster gets reordered as mons faint
+    pub team: Vec<Set>,
 }
 
 #[derive(Debug, Clone)]
@@ -71,19 +94,21 @@ impl IndexMut<HazardId> for HazardBlock {
 
 impl Player {
     pub fn new(ai: bool, weather: Rc<RefCell<Option<WeatherId>>>) -> Self {
+        Self::with_team(ai, default_team(), weather)
+    }
+
+    pub fn with_team(ai: bool, team: Vec<Set>, weather: Rc<RefCell<Option<WeatherId>>>) -> Self {
         Self {
             name: "test",
             ai,
             hazards: HazardBlock::default(),
-            roster: PointerVec::from(vec![
-                Pokemon::preset(PokeId::Jirachi, Rc::clone(&weather)),
-                Pokemon::preset(PokeId::Tyranitar, Rc::clone(&weather)),
-                Pokemon::preset(PokeId::Heatran, Rc::clone(&weather)),
-                Pokemon::preset(PokeId::Breloom, Rc::clone(&weather)),
-                Pokemon::preset(PokeId::Zapdos, Rc::clone(&weather)),
-                Pokemon::preset(PokeId::Starmie, weather),
-            ]),
+            roster: PointerVec::from(
+                team.iter()
+                    .map(|set| Pokemon::from_set(set, Rc::clone(&weather)))
+                    .collect::<Vec<_>>(),
+            ),
             inputs: Vec::new(),
+            team,
         }
     }
 
@@ -106,16 +131,25 @@ impl Player {
                     }),
             );
 
-            // If the game is not waiting for a switch input, add active pokemon's remaining moves
+            // If the game is not waiting for a switch input, add active pokemon's moves with pp left
+            // or only the move it is choice locked into
             if *state != GameState::AwaitingSwitch {
-                out.extend(
-                    self.roster[active_idx]
-                        .moves
-                        .living()
-                        .iter()
-                        .enumerate()
-                        .map(|(idx, _)| MoveSelection::Move(idx)),
-                );
+                let active_mon = &self.roster[active_idx];
+                let lock = active_mon.choice_lock();
+                let moves: Vec<MoveSelection> = active_mon
+                    .moves
+                    .living()
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, move_)| move_.pp.data > 0 && lock.is_none_or(|k| k == *idx))
+                    .map(|(idx, _)| MoveSelection::Move(idx))
+                    .collect();
+
+                if moves.is_empty() {
+                    out.push(MoveSelection::Struggle);
+                } else {
+                    out.extend(moves);
+                }
             }
 
             out
@@ -133,6 +167,11 @@ impl Player {
     pub fn has_active(&self) -> bool {
         self.roster.active.is_some()
     }
+
+    /// the active mon fainted and there's one left to send out
+    pub fn needs_replacement(&self) -> bool {
+        !self.has_active() && self.roster.dead > 0
+    }
 }
 
 impl Display for Player {
@@ -2,28 +2,32 @@ use std::cell::RefCell;
 use std::fmt::Display;
 use std::rc::Rc;
 
-use rand::{thread_rng, Rng};
-
 use crate::bounded_i32::BoundedI32;
 use crate::game::{MoveSelection, WeatherId};
 use crate::moves::Move;
 use crate::poketype::Poketype;
 use crate::preset::PokeId;
 use crate::selvec::PointerVec;
-use crate::stat::{StatBlock, StatId};
-use crate::status::{Status, StatusBlock};
-use crate::trigger::{Ability, Item};
+use crate::stat::{Spread, StatBlock, StatId};
+use crate::status::StatusBlock;
+use crate::trigger::{Ability, Item, Trigger};
 
 #[derive(Debug, Clone, Default)]
 pub struct Pokemon {
     pub ability: Ability,
     pub hp: BoundedI32,
     pub id: PokeId,
+    pub spread: Spread,
     pub item: Rc<RefCell<Option<Item>>>,
+    /// the item the mon used up, if any
+    pub consumed: Option<Item>,
+    /// Knock Off took its item, it can't get another one until it switches out
+    pub item_knocked_off: bool,
     pub moves: PointerVec<Move>,
     pub poketype: Rc<RefCell<Poketype>>,
     pub stats: StatBlock,
     pub status: Rc<RefCell<StatusBlock>>,
+    pub struggle: Move,
 }
 
 impl Display for Pokemon {
@@ -42,10 +46,10 @@ impl Display for Pokemon {
             self.hp.max,
             self.hp.data * 100 / self.hp.max,
             self.poketype.borrow(),
-            if let Some(item) = *self.item.borrow() {
-                format!("Item: {}\n", item)
-            } else {
-                String::new()
+            match (*self.item.borrow(), self.consumed) {
+                (Some(item), _) => format!("Item: {}\n", item),
+                (None, Some(item)) => format!("Item: {} (consumed)\n", item),
+                (None, None) => String::new(),
             },
             self.ability,
             self.stats[StatId::Atk],
@@ -58,35 +62,69 @@ impl Display for Pokemon {
 }
 
 impl Pokemon {
+    /// stats come from the species' base stats and the spread
     pub fn new(
         ability: Ability,
-        hp: BoundedI32,
         id: PokeId,
         item: Option<Item>,
         moves: PointerVec<Move>,
         poketype: Poketype,
-        stats: [i32; 5],
+        spread: Spread,
         weather: Rc<RefCell<Option<WeatherId>>>,
     ) -> Self {
+        let [hp, stats @ ..] = spread.calc(id.base_stats());
         let item = Rc::new(RefCell::new(item));
+        // the active move is the one a choice item locks into
+        let mut moves = moves;
+        moves.active = None;
         let poketype = Rc::new(RefCell::new(poketype));
         let status = Rc::new(RefCell::new(StatusBlock::new()));
         let stats = StatBlock::new(
             stats,
-            Rc::clone(&poketype),
+            ability,
             Rc::clone(&item),
             weather,
             Rc::clone(&status),
         );
         Self {
             ability,
-            hp,
+            hp: BoundedI32::zero(hp),
             id,
+            spread,
             item,
+            consumed: None,
+            item_knocked_off: false,
             moves,
             poketype,
             stats,
             status,
+            struggle: Move::struggle(),
+        }
+    }
+
+    /// leaving the field drops stat stages, volatile statuses, any choice lock and lets a
+    /// knocked off mon hold items again
+    pub fn switch_out(&mut self) {
+        self.stats.reset_stages();
+        self.status.borrow_mut().clear_volatile();
+        self.moves.active = None;
+        self.item_knocked_off = false;
+    }
+
+    /// the ability and held item, in the order their hooks run
+    pub fn triggers(&self) -> Vec<Box<dyn Trigger>> {
+        let mut out: Vec<Box<dyn Trigger>> = vec![Box::new(self.ability)];
+        if let Some(item) = *self.item.borrow() {
+            out.push(Box::new(item));
+        }
+        out
+    }
+
+    /// the move a choice item has locked the mon into
+    pub fn choice_lock(&self) -> Option<usize> {
+        match *self.item.borrow() {
+            Some(item) if item.is_choice() => self.moves.active,
+            _ => None,
         }
     }
 
@@ -94,74 +132,7 @@ impl Pokemon {
         match move_ {
             MoveSelection::Switch(_) => None,
             MoveSelection::Move(idx) => Some(&self.moves[*idx]),
+            MoveSelection::Struggle => Some(&self.struggle),
         }
     }
-    /// if return.1 skip move, if Some always log message
-    pub fn exec_moveskip(&mut self) -> (Option<String>, bool) {
-        let mut rand = thread_rng();
-        let mut removed_statuses = Vec::new();
-        let mut message = None;
-        let mut skip_turn = false;
-        let mut statusblock = self.status.try_borrow_mut().unwrap();
-        for status in &[
-            Status::Paralyse,
-            Status::Sleep,
-            Status::Freeze,
-            Status::Confusion,
-            Status::Flinch,
-        ] {
-            if let Some(value) = statusblock.data.get_mut(status) {
-                match status {
-                    Status::Paralyse => {
-                        if rand.gen_range(0..=3) == 0 {
-                            message = Some(format!("{} was full para", self.id));
-                            skip_turn = true;
-                        }
-                    }
-                    Status::Sleep => {
-                        if *value == 0 {
-                            Some(format!("{} woke up!", self.id));
-                            removed_statuses.push(status);
-                        } else {
-                            *value -= 1;
-                            message = Some(format!("{} was sleeping", self.id));
-                            skip_turn = true;
-                        }
-                    }
-                    Status::Freeze => {
-                        if rand.gen_range(0..=9) == 0 {
-                            message = Some(format!("{} thawed!", self.id));
-                            removed_statuses.push(status);
-                        } else {
-                            message = Some(format!("{} is frozen", self.id));
-                            skip_turn = true;
-                        }
-                    }
-                    Status::Confusion => {
-                        if *value == 0 {
-                            message = Some(format!("{} snapped out of confusion", self.id));
-                            removed_statuses.push(status);
-                        } else if rand.gen_range(0..=1) == 0 {
-                            message = Some(format!("{} hit itself in confusion(todo)", self.id));
-                            skip_turn = true;
-                            *value -= 1;
-                        } else {
-                            *value -= 1;
-                        }
-                    }
-                    Status::Flinch => {
-                        message = Some(format!("{} flinched", self.id));
-                        removed_statuses.push(status);
-                    }
-                    _ => {}
-                }
-            }
-        }
-        if let Ok(mut data) = self.status.try_borrow_mut() {
-            for status in removed_statuses {
-                data.data.remove(status);
-            }
-        }
-        (message, skip_turn)
-    }
 }
@@ -1,6 +1,8 @@
 use std::fmt::Display;
 
 use crate::pokemon::Pokemon;
+use crate::trigger::Ability;
+use serde::{Deserialize, Serialize};
 
 /// Basically just a database of the type chart
 
@@ -38,7 +40,7 @@ impl Poketype {
     }
 }
 
-#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
 pub enum Type {
     #[default]
     Normal,
@@ -61,6 +63,29 @@ pub enum Type {
     Typeless,
 }
 
+impl Type {
+    pub const ALL: [Type; 18] = [
+        Type::Normal,
+        Type::Fire,
+        Type::Water,
+        Type::Electric,
+        Type::Grass,
+        Type::Ice,
+        Type::Fighting,
+        Type::Poison,
+        Type::Ground,
+        Type::Flying,
+        Type::Psychic,
+        Type::Bug,
+        Type::Rock,
+        Type::Ghost,
+        Type::Dragon,
+        Type::Dark,
+        Type::Steel,
+        Type::Typeless,
+    ];
+}
+
 impl Display for Type {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{self:?}",)
@@ -199,12 +224,18 @@ impl Type {
     }
 
     pub fn calc_eff(&self, defending_type: &Poketype) -> f32 {
+        self.calc_eff_split(defending_type).iter().product()
+    }
+
+    /// effectiveness against each of the defending types in order, the damage formula
+    /// truncates after each one
+    pub fn calc_eff_split(&self, defending_type: &Poketype) -> Vec<f32> {
         match defending_type {
-            Poketype::Mono(target) => f32::from(self.effectiveness(&target)),
-            Poketype::Dual(targets) => {
-                f32::from(self.effectiveness(&targets[0]))
-                    * f32::from(self.effectiveness(&targets[1]))
-            }
+            Poketype::Mono(target) => vec![f32::from(self.effectiveness(target))],
+            Poketype::Dual(targets) => vec![
+                f32::from(self.effectiveness(&targets[0])),
+                f32::from(self.effectiveness(&targets[1])),
+            ],
         }
     }
 }
@@ -217,7 +248,21 @@ impl Pokemon {
         }
     }
 
+    /// flying types and levitating mons dodge ground moves and the hazards on the ground
+    pub fn is_grounded(&self) -> bool {
+        !self.has_type(&Type::Flying) && self.ability != Ability::Levitate
+    }
+
     pub fn type_eff(&self, target: &Type) -> f32 {
-        target.calc_eff(&self.poketype.borrow())
+        self.type_eff_split(target).iter().product()
+    }
+
+    /// [`Type::calc_eff_split`] against this mon, ground moves can't touch it unless it's
+    /// grounded
+    pub fn type_eff_split(&self, target: &Type) -> Vec<f32> {
+        if *target == Type::Ground && !self.is_grounded() {
+            return vec![0.0];
+        }
+        target.calc_eff_split(&self.poketype.borrow())
     }
 }
@@ -2,136 +2,82 @@ use std::cell::RefCell;
 use std::fmt::Display;
 use std::rc::Rc;
 
-use crate::bounded_i32::BoundedI32;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::dex::dex;
 use crate::game::WeatherId;
 use crate::moves::{Move, MoveId};
 use crate::pokemon::Pokemon;
-use crate::poketype::{Poketype, Type};
 use crate::selvec::PointerVec;
+use crate::stat::Spread;
 use crate::trigger::Ability;
 use crate::trigger::Item;
 
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
-pub enum PokeId {
-    #[default]
-    Jirachi,
-    Zapdos,
-    Starmie,
-    Heatran,
-    Tyranitar,
-    Breloom,
-}
+/// index into the loaded species, saved by name so replays survive reordering the data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PokeId(pub usize);
 
 impl Display for PokeId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                PokeId::Jirachi => "Jirachi",
-                PokeId::Zapdos => "Zapdos",
-                PokeId::Starmie => "Starmie",
-                PokeId::Heatran => "Heatran",
-                PokeId::Tyranitar => "Tyranitar",
-                PokeId::Breloom => "Breloom",
-            }
-        )
+        write!(f, "{}", dex().species(*self).name)
+    }
+}
+
+impl Serialize for PokeId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&dex().species(*self).name)
+    }
+}
+
+impl<'de> Deserialize<'de> for PokeId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        PokeId::from_name(&name)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown species {}", name)))
+    }
+}
+
+impl PokeId {
+    pub fn from_name(name: &str) -> Option<Self> {
+        dex().species_id(name)
     }
+
+    /// HP, Atk, Def, SpA, SpD, Spe
+    pub fn base_stats(&self) -> [i32; 6] {
+        dex().species(*self).base_stats
+    }
+}
+
+/// everything needed to build one team member, see [`crate::paste`] for reading and writing
+/// these as Showdown pastes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Set {
+    pub species: PokeId,
+    pub ability: Ability,
+    pub item: Option<Item>,
+    pub moves: Vec<MoveId>,
+    pub spread: Spread,
 }
 
 impl Pokemon {
     pub fn preset(value: PokeId, weather: Rc<RefCell<Option<WeatherId>>>) -> Self {
-        match value {
-            PokeId::Jirachi => Pokemon::new(
-                Ability::SereneGrace,
-                BoundedI32::zero(404),
-                value,
-                Some(Item::Leftovers),
-                PointerVec::from(vec![
-                    Move::from(MoveId::IronHead),
-                    Move::from(MoveId::BodySlam),
-                    Move::from(MoveId::Firepunch),
-                    Move::from(MoveId::Refresh),
-                ]),
-                Poketype::Dual([Type::Steel, Type::Psychic]),
-                [249, 236, 212, 286, 263],
-                weather,
-            ),
-            PokeId::Zapdos => Pokemon::new(
-                Ability::Pressure,
-                BoundedI32::zero(383),
-                value,
-                Some(Item::Leftovers),
-                PointerVec::from(vec![
-                    Move::from(MoveId::Thunderbolt),
-                    Move::from(MoveId::Roost),
-                    Move::from(MoveId::Hpice),
-                    Move::from(MoveId::Uturn),
-                ]),
-                Poketype::Dual([Type::Electric, Type::Flying]),
-                [193, 288, 286, 216, 244],
-                weather,
-            ),
-            PokeId::Starmie => Pokemon::new(
-                Ability::NaturalCure,
-                BoundedI32::zero(261),
-                value,
-                Some(Item::Leftovers),
-                PointerVec::from(vec![
-                    Move::from(MoveId::Hydropump),
-                    Move::from(MoveId::Thunderwave),
-                    Move::from(MoveId::Icebeam),
-                    Move::from(MoveId::Rapidspin),
-                ]),
-                Poketype::Dual([Type::Water, Type::Psychic]),
-                [139, 207, 299, 206, 361],
-                weather,
-            ),
-            PokeId::Heatran => Pokemon::new(
-                Ability::Flashfire,
-                BoundedI32::zero(261),
-                value,
-                Some(Item::Leftovers),
-                PointerVec::from(vec![
-                    Move::from(MoveId::Stealthrock),
-                    Move::from(MoveId::Fireblast),
-                    Move::from(MoveId::Earthpower),
-                    Move::from(MoveId::Explosion),
-                ]),
-                Poketype::Dual([Type::Fire, Type::Steel]),
-                [193, 247, 326, 248, 253],
-                weather,
-            ),
-            PokeId::Tyranitar => Pokemon::new(
-                Ability::SandStream,
-                BoundedI32::zero(341),
-                value,
-                Some(Item::Leftovers),
-                PointerVec::from(vec![
-                    Move::from(MoveId::Crunch),
-                    Move::from(MoveId::Pursuit),
-                    Move::from(MoveId::Superpower),
-                    Move::from(MoveId::Stoneedge),
-                ]),
-                Poketype::Dual([Type::Rock, Type::Dark]),
-                [367, 256, 206, 237, 243],
-                weather,
-            ),
-            PokeId::Breloom => Pokemon::new(
-                Ability::SandStream,
-                BoundedI32::zero(280),
-                value,
-                Some(Item::ToxicOrb),
-                PointerVec::from(vec![
-                    Move::from(MoveId::Spore),
-                    Move::from(MoveId::Superpower),
-                    Move::from(MoveId::Seedbomb),
-                    Move::from(MoveId::Machpunch),
-                ]),
-                Poketype::Dual([Type::Grass, Type::Fighting]),
-                [393, 196, 140, 156, 222],
-                weather,
+        Self::from_set(&dex().species(value).preset, weather)
+    }
+
+    pub fn from_set(set: &Set, weather: Rc<RefCell<Option<WeatherId>>>) -> Self {
+        Pokemon::new(
+            set.ability,
+            set.species,
+            set.item,
+            PointerVec::from(
+                set.moves
+                    .iter()
+                    .map(|id| Move::from(*id))
+                    .collect::<Vec<_>>(),
             ),
-        }
+            dex().species(set.species).poketype,
+            set.spread,
+            weather,
+        )
     }
 }
@@ -0,0 +1,68 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppResult;
+use crate::game::{Game, MoveSelection};
+use crate::preset::Set;
+
+/// Everything needed to re-simulate a battle: the rng seed, both starting teams and every
+/// input each player made.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub teams: [Vec<Set>; 2],
+    pub inputs: [Vec<MoveSelection>; 2],
+}
+
+impl From<&Game> for Replay {
+    fn from(game: &Game) -> Self {
+        Self {
+            seed: game.seed,
+            teams: [game.players[0].team.clone(), game.players[1].team.clone()],
+            inputs: [
+                game.players[0].inputs.clone(),
+                game.players[1].inputs.clone(),
+            ],
+        }
+    }
+}
+
+impl Replay {
+    pub fn load(path: impl AsRef<Path>) -> AppResult<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> AppResult<()> {
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Re-simulates the battle through `Game::execute_turn`. Player 1's inputs drive the game,
+    /// the bot's are rolled again from the seed and checked against the recording every turn.
+    pub fn play(&self) -> AppResult<Game> {
+        let mut game = Game::with_teams(self.seed, self.teams.clone());
+
+        for (turn, input) in self.inputs[0].iter().enumerate() {
+            if !game.players[0]
+                .list_valid_inputs(&game.state)
+                .contains(input)
+            {
+                return Err(format!("replay input {} ({:?}) is not valid", turn, input).into());
+            }
+            game.players[0].inputs.push(*input);
+            game.execute_turn();
+            if !self.inputs[1].starts_with(&game.players[1].inputs) {
+                return Err(
+                    format!("replay diverged from the bot's inputs at input {}", turn).into(),
+                );
+            }
+        }
+
+        if game.players[1].inputs != self.inputs[1] {
+            return Err("replay diverged from the recorded bot inputs".into());
+        }
+        Ok(game)
+    }
+}
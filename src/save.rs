@@ -0,0 +1,219 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppResult;
+use crate::battle_event::BattleEvent;
+use crate::bounded_i32::BoundedI32;
+use crate::game::{Game, GameState, HazardId, MoveSelection, WeatherId};
+use crate::player::Player;
+use crate::pokemon::Pokemon;
+use crate::preset::Set;
+use crate::selvec::PointerVec;
+use crate::stat::StatId;
+use crate::status::{Status, StatusBlock};
+use crate::trigger::Item;
+
+/// hazards in the order they are saved
+const HAZARDS: [HazardId; 3] = [
+    HazardId::StealthRock,
+    HazardId::Spikes,
+    HazardId::ToxicSpikes,
+];
+
+/// A battle in progress, written out exactly as it stands so it can be picked back up later.
+/// Unlike a [`crate::replay::Replay`] nothing is re-simulated on load.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Save {
+    pub players: [PlayerSave; 2],
+    /// index of the player the game is currently acting for
+    pub active: Option<usize>,
+    pub turn_count: i32,
+    pub weather: Option<WeatherId>,
+    pub log: Vec<Vec<BattleEvent>>,
+    pub state: GameState,
+    pub prev_state: Vec<GameState>,
+    pub seed: u64,
+    pub rng: ChaCha12Rng,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSave {
+    pub team: Vec<Set>,
+    /// in roster order, which gets shuffled as mons faint
+    pub roster: Vec<MonSave>,
+    pub active: Option<usize>,
+    pub dead: usize,
+    /// stealth rock, spikes and toxic spikes layers
+    pub hazards: [i32; 3],
+    pub inputs: Vec<MoveSelection>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonSave {
+    /// the mon as it is now, the item may differ from the team's after Trick or Knock Off
+    pub set: Set,
+    pub hp: i32,
+    pub consumed: Option<Item>,
+    #[serde(default)]
+    pub item_knocked_off: bool,
+    pub pp: Vec<i32>,
+    /// the move a choice item locked into
+    pub locked: Option<usize>,
+    /// in [`StatId::ALL`] order
+    pub stages: [i32; 7],
+    pub status: HashMap<Status, i8>,
+    pub nv: Option<Status>,
+}
+
+impl From<&Game> for Save {
+    fn from(game: &Game) -> Self {
+        Self {
+            players: [
+                PlayerSave::from(&game.players[0]),
+                PlayerSave::from(&game.players[1]),
+            ],
+            active: game.players.active,
+            turn_count: game.turn_count,
+            weather: *game.weather.borrow(),
+            log: game.log.clone(),
+            state: game.state,
+            prev_state: game.prev_state.clone(),
+            seed: game.seed,
+            rng: game.rng.clone(),
+        }
+    }
+}
+
+impl From<&Player> for PlayerSave {
+    fn from(player: &Player) -> Self {
+        Self {
+            team: player.team.clone(),
+            roster: player.roster.data.iter().map(MonSave::from).collect(),
+            active: player.roster.active,
+            dead: player.roster.dead,
+            hazards: HAZARDS.map(|hazard| player.hazards[hazard].data),
+            inputs: player.inputs.clone(),
+        }
+    }
+}
+
+impl From<&Pokemon> for MonSave {
+    fn from(mon: &Pokemon) -> Self {
+        let status = mon.status.borrow();
+        Self {
+            set: Set {
+                species: mon.id,
+                ability: mon.ability,
+                item: *mon.item.borrow(),
+                moves: mon.moves.data.iter().map(|move_| move_.id).collect(),
+                spread: mon.spread,
+            },
+            hp: mon.hp.data,
+            consumed: mon.consumed,
+            item_knocked_off: mon.item_knocked_off,
+            pp: mon.moves.data.iter().map(|move_| move_.pp.data).collect(),
+            locked: mon.moves.active,
+            stages: StatId::ALL.map(|stat| mon.stats[stat].stage()),
+            status: status.data.clone(),
+            nv: status.nv,
+        }
+    }
+}
+
+impl Save {
+    pub fn load(path: impl AsRef<Path>) -> AppResult<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> AppResult<()> {
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Rebuilds the battle, failing rather than panicking later if the save doesn't hang
+    /// together.
+    pub fn restore(self) -> AppResult<Game> {
+        let weather = Rc::new(RefCell::new(self.weather));
+        let [player1, player2] = self.players;
+        let players = vec![
+            player1.restore(false, Rc::clone(&weather))?,
+            player2.restore(true, Rc::clone(&weather))?,
+        ];
+        if self.active.is_some_and(|idx| idx >= players.len()) {
+            return Err("save has an invalid active player".into());
+        }
+        if self.log.is_empty() {
+            return Err("save has an empty log".into());
+        }
+
+        Ok(Game {
+            players: PointerVec {
+                active: self.active,
+                data: players,
+                dead: 2,
+            },
+            turn_count: self.turn_count,
+            weather,
+            log: self.log,
+            state: self.state,
+            prev_state: self.prev_state,
+            seed: self.seed,
+            rng: self.rng,
+        })
+    }
+}
+
+impl PlayerSave {
+    fn restore(self, ai: bool, weather: Rc<RefCell<Option<WeatherId>>>) -> AppResult<Player> {
+        let mut player = Player::with_team(ai, self.team, Rc::clone(&weather));
+        player.roster.data = self
+            .roster
+            .into_iter()
+            .map(|mon| mon.restore(Rc::clone(&weather)))
+            .collect::<AppResult<_>>()?;
+        if self.dead > player.roster.data.len() || self.active.is_some_and(|idx| idx >= self.dead) {
+            return Err("save has an invalid roster".into());
+        }
+        player.roster.active = self.active;
+        player.roster.dead = self.dead;
+        for (hazard, layers) in HAZARDS.into_iter().zip(self.hazards) {
+            let block = &mut player.hazards[hazard];
+            *block = BoundedI32::new(layers, block.min, block.max);
+        }
+        player.inputs = self.inputs;
+        Ok(player)
+    }
+}
+
+impl MonSave {
+    fn restore(self, weather: Rc<RefCell<Option<WeatherId>>>) -> AppResult<Pokemon> {
+        let mut mon = Pokemon::from_set(&self.set, weather);
+        if self.pp.len() != mon.moves.data.len()
+            || self.locked.is_some_and(|idx| idx >= mon.moves.data.len())
+        {
+            return Err(format!("save has invalid moves for {}", mon.id).into());
+        }
+
+        mon.hp = BoundedI32::new(self.hp, 0, mon.hp.max);
+        mon.consumed = self.consumed;
+        mon.item_knocked_off = self.item_knocked_off;
+        for (move_, pp) in mon.moves.data.iter_mut().zip(self.pp) {
+            move_.pp = BoundedI32::new(pp, 0, move_.pp.max);
+        }
+        mon.moves.active = self.locked;
+        for (stat, stage) in StatId::ALL.into_iter().zip(self.stages) {
+            mon.stats[stat].alter(stage);
+        }
+        *mon.status.borrow_mut() = StatusBlock {
+            data: self.status,
+            nv: self.nv,
+        };
+        Ok(mon)
+    }
+}
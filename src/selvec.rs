@@ -1,9 +1,7 @@
 use std::ops::{Index, IndexMut};
 
-use crate::{
-    player::Player,
-    EmptyResult,
-};
+use crate::{player::Player, EmptyResult};
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Default)]
 pub struct PointerVec<T> {
@@ -21,7 +19,6 @@ where
             dead: value.len(),
             data: value,
             active: Some(0),
-            ..Default::default()
         }
     }
 }
@@ -40,7 +37,7 @@ impl<T> IndexMut<usize> for PointerVec<T> {
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default, Serialize, Deserialize)]
 #[repr(usize)]
 pub enum PlayerId {
     #[default]
@@ -48,28 +45,30 @@ pub enum PlayerId {
     Inactive,
 }
 
+impl PointerVec<Player> {
+    /// resolves a relative player id to its index in `data`
+    pub fn idx(&self, id: PlayerId) -> usize {
+        let active = self.active.expect("No active player");
+
+        match id {
+            PlayerId::Active => active,
+            PlayerId::Inactive => (active + 1) % 2,
+        }
+    }
+}
+
 impl Index<PlayerId> for PointerVec<Player> {
     type Output = Player;
 
     fn index(&self, index: PlayerId) -> &Self::Output {
-        let active = self.active.expect("No active player");
-
-        match index {
-            PlayerId::Active => &self.data[active],
-            PlayerId::Inactive => &self.data[(active + 1) % 2],
-        }
+        &self.data[self.idx(index)]
     }
 }
 
 impl IndexMut<PlayerId> for PointerVec<Player> {
     fn index_mut(&mut self, index: PlayerId) -> &mut Self::Output {
-        let active = self.active.expect("No active player");
-
-        match index {
-            // unwrap because there is always an active player
-            PlayerId::Active => &mut self.data[active],
-            PlayerId::Inactive => &mut self.data[(active + 1) % 2],
-        }
+        let idx = self.idx(index);
+        &mut self.data[idx]
     }
 }
 
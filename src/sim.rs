@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::game::{Game, GameResult, GameState};
+use crate::preset::PokeId;
+
+/// games still running after this many turns are abandoned
+const MAX_TURNS: i32 = 1000;
+
+/// Aggregate results of a batch of bot vs bot games.
+#[derive(Debug, Default)]
+pub struct SimReport {
+    pub games: usize,
+    pub wins: [usize; 2],
+    pub ties: usize,
+    pub unfinished: usize,
+    pub total_turns: usize,
+    /// per species (times fielded, times fainted)
+    pub faints: HashMap<PokeId, (usize, usize)>,
+}
+
+impl SimReport {
+    pub fn avg_turns(&self) -> f32 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.total_turns as f32 / self.games as f32
+        }
+    }
+
+    fn record(&mut self, game: &Game) {
+        self.games += 1;
+        self.total_turns += game.turn_count as usize;
+        match game.state {
+            GameState::Completed(GameResult::Winner(idx)) => self.wins[idx] += 1,
+            GameState::Completed(GameResult::Tie) => self.ties += 1,
+            _ => self.unfinished += 1,
+        }
+
+        for player in &game.players.data {
+            // fainted mons are swapped behind the living ones by PointerVec::kill
+            for (idx, mon) in player.roster.data.iter().enumerate() {
+                let entry = self.faints.entry(mon.id).or_default();
+                entry.0 += 1;
+                if idx >= player.roster.dead {
+                    entry.1 += 1;
+                }
+            }
+        }
+    }
+}
+
+impl Display for SimReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Games: {}", self.games)?;
+        writeln!(f, "Player 1 wins: {}", self.wins[0])?;
+        writeln!(f, "Player 2 wins: {}", self.wins[1])?;
+        writeln!(f, "Ties: {}", self.ties)?;
+        if self.unfinished > 0 {
+            writeln!(f, "Unfinished: {}", self.unfinished)?;
+        }
+        writeln!(f, "Average turns: {:.2}", self.avg_turns())?;
+        writeln!(f, "Faint rates:")?;
+
+        let mut faints: Vec<_> = self.faints.iter().collect();
+        faints.sort_by_key(|(id, _)| id.to_string());
+        for (id, (fielded, fainted)) in faints {
+            writeln!(
+                f,
+                "  {:12}{:6.2}%",
+                id.to_string(),
+                *fainted as f32 * 100.0 / *fielded as f32
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// plays a single game to completion with both players picking random valid inputs
+pub fn run_game(seed: u64) -> Game {
+    let mut game = Game::with_seed(seed);
+    // player 1 stands in for a human so it must not draw from the battle rng
+    let mut agent = StdRng::seed_from_u64(!seed);
+    // execute_turn drives player 2 itself and only returns when player 1 must act
+    while !matches!(game.state, GameState::Completed(_)) && game.turn_count < MAX_TURNS {
+        let choices = game.players[0].list_valid_inputs(&game.state);
+        game.players[0]
+            .inputs
+            .push(*choices.choose(&mut agent).unwrap());
+        game.execute_turn();
+    }
+    game
+}
+
+/// runs `games` games, seeding game `n` with `seed + n`
+pub fn simulate(games: usize, seed: u64) -> SimReport {
+    let mut report = SimReport::default();
+    for n in 0..games {
+        report.record(&run_game(seed.wrapping_add(n as u64)));
+    }
+    report
+}
@@ -1,18 +1,18 @@
 use core::fmt;
 use std::{
     cell::RefCell,
-    cmp::Ordering,
     ops::{Index, IndexMut},
     rc::Rc,
 };
 
 use crate::{
     bounded_i32::BoundedI32,
+    dex::to_id,
     game::WeatherId,
-    poketype::Poketype,
     status::{Status, StatusBlock},
-    trigger::Item,
+    trigger::{Ability, Item, Trigger},
 };
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Default, Clone)]
 pub struct Stat {
@@ -20,8 +20,8 @@ pub struct Stat {
     base: i32,
     stage: BoundedI32,
     weather: Rc<RefCell<Option<WeatherId>>>,
-    poketype: Rc<RefCell<Poketype>>,
     item: Rc<RefCell<Option<Item>>>,
+    ability: Ability,
     status: Rc<RefCell<StatusBlock>>,
 }
 
@@ -32,12 +32,14 @@ pub struct StatBlock {
     spa: Stat,
     spd: Stat,
     spe: Stat,
+    acc: Stat,
+    eva: Stat,
 }
 
 impl StatBlock {
     pub fn new(
         values: [i32; 5],
-        poketype: Rc<RefCell<Poketype>>,
+        ability: Ability,
         item: Rc<RefCell<Option<Item>>>,
         weather: Rc<RefCell<Option<WeatherId>>>,
         status: Rc<RefCell<StatusBlock>>,
@@ -46,37 +48,62 @@ impl StatBlock {
             atk: Stat::new(
                 values[0],
                 StatId::Atk,
-                Rc::clone(&poketype),
                 Rc::clone(&item),
+                ability,
                 Rc::clone(&weather),
                 Rc::clone(&status),
             ),
             def: Stat::new(
                 values[1],
                 StatId::Def,
-                Rc::clone(&poketype),
                 Rc::clone(&item),
+                ability,
                 Rc::clone(&weather),
                 Rc::clone(&status),
             ),
             spa: Stat::new(
                 values[2],
                 StatId::Spa,
-                Rc::clone(&poketype),
                 Rc::clone(&item),
+                ability,
                 Rc::clone(&weather),
                 Rc::clone(&status),
             ),
             spd: Stat::new(
                 values[3],
                 StatId::Spd,
-                Rc::clone(&poketype),
                 Rc::clone(&item),
+                ability,
                 Rc::clone(&weather),
                 Rc::clone(&status),
             ),
-            spe: Stat::new(values[4], StatId::Spe, poketype, item, weather, status),
-            ..Default::default()
+            spe: Stat::new(
+                values[4],
+                StatId::Spe,
+                Rc::clone(&item),
+                ability,
+                Rc::clone(&weather),
+                Rc::clone(&status),
+            ),
+            // accuracy and evasion only have stages, their base is a percentage
+            acc: Stat::new(
+                100,
+                StatId::Acc,
+                Rc::clone(&item),
+                ability,
+                Rc::clone(&weather),
+                Rc::clone(&status),
+            ),
+            eva: Stat::new(100, StatId::Eva, item, ability, weather, status),
+        }
+    }
+}
+
+impl StatBlock {
+    /// drops every stage back to 0
+    pub fn reset_stages(&mut self) {
+        for stat in StatId::ALL {
+            self[stat].reset();
         }
     }
 }
@@ -90,6 +117,8 @@ impl Index<StatId> for StatBlock {
             StatId::Spa => &self.spa,
             StatId::Spd => &self.spd,
             StatId::Spe => &self.spe,
+            StatId::Acc => &self.acc,
+            StatId::Eva => &self.eva,
         }
     }
 }
@@ -102,11 +131,13 @@ impl IndexMut<StatId> for StatBlock {
             StatId::Spa => &mut self.spa,
             StatId::Spd => &mut self.spd,
             StatId::Spe => &mut self.spe,
+            StatId::Acc => &mut self.acc,
+            StatId::Eva => &mut self.eva,
         }
     }
 }
 
-#[derive(Hash, PartialEq, Eq, Clone, Copy, Debug, Default)]
+#[derive(Hash, PartialEq, Eq, Clone, Copy, Debug, Default, Serialize, Deserialize)]
 pub enum StatId {
     #[default]
     Atk,
@@ -114,6 +145,201 @@ pub enum StatId {
     Spa,
     Spd,
     Spe,
+    Acc,
+    Eva,
+}
+
+impl StatId {
+    pub const ALL: [StatId; 7] = [
+        StatId::Atk,
+        StatId::Def,
+        StatId::Spa,
+        StatId::Spd,
+        StatId::Spe,
+        StatId::Acc,
+        StatId::Eva,
+    ];
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
+pub enum Nature {
+    #[default]
+    Hardy,
+    Lonely,
+    Brave,
+    Adamant,
+    Naughty,
+    Bold,
+    Docile,
+    Relaxed,
+    Impish,
+    Lax,
+    Timid,
+    Hasty,
+    Serious,
+    Jolly,
+    Naive,
+    Modest,
+    Mild,
+    Quiet,
+    Bashful,
+    Rash,
+    Calm,
+    Gentle,
+    Sassy,
+    Careful,
+    Quirky,
+}
+
+impl fmt::Display for Nature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl Nature {
+    pub const ALL: [Nature; 25] = [
+        Nature::Hardy,
+        Nature::Lonely,
+        Nature::Brave,
+        Nature::Adamant,
+        Nature::Naughty,
+        Nature::Bold,
+        Nature::Docile,
+        Nature::Relaxed,
+        Nature::Impish,
+        Nature::Lax,
+        Nature::Timid,
+        Nature::Hasty,
+        Nature::Serious,
+        Nature::Jolly,
+        Nature::Naive,
+        Nature::Modest,
+        Nature::Mild,
+        Nature::Quiet,
+        Nature::Bashful,
+        Nature::Rash,
+        Nature::Calm,
+        Nature::Gentle,
+        Nature::Sassy,
+        Nature::Careful,
+        Nature::Quirky,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|nature| to_id(&nature.to_string()) == to_id(name))
+    }
+
+    /// the raised and lowered stat, neutral natures have neither
+    pub fn effect(&self) -> Option<(StatId, StatId)> {
+        use StatId::*;
+        match self {
+            Nature::Lonely => Some((Atk, Def)),
+            Nature::Brave => Some((Atk, Spe)),
+            Nature::Adamant => Some((Atk, Spa)),
+            Nature::Naughty => Some((Atk, Spd)),
+            Nature::Bold => Some((Def, Atk)),
+            Nature::Relaxed => Some((Def, Spe)),
+            Nature::Impish => Some((Def, Spa)),
+            Nature::Lax => Some((Def, Spd)),
+            Nature::Timid => Some((Spe, Atk)),
+            Nature::Hasty => Some((Spe, Def)),
+            Nature::Jolly => Some((Spe, Spa)),
+            Nature::Naive => Some((Spe, Spd)),
+            Nature::Modest => Some((Spa, Atk)),
+            Nature::Mild => Some((Spa, Def)),
+            Nature::Quiet => Some((Spa, Spe)),
+            Nature::Rash => Some((Spa, Spd)),
+            Nature::Calm => Some((Spd, Atk)),
+            Nature::Gentle => Some((Spd, Def)),
+            Nature::Sassy => Some((Spd, Spe)),
+            Nature::Careful => Some((Spd, Spa)),
+            Nature::Hardy | Nature::Docile | Nature::Serious | Nature::Bashful | Nature::Quirky => {
+                None
+            }
+        }
+    }
+
+    /// percentage applied to the stat
+    pub fn modifier(&self, stat: StatId) -> i32 {
+        match self.effect() {
+            Some((up, _)) if up == stat => 110,
+            Some((_, down)) if down == stat => 90,
+            _ => 100,
+        }
+    }
+}
+
+/// Everything besides the species that goes into a mon's stats. EVs and IVs are ordered
+/// HP, Atk, Def, SpA, SpD, Spe.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Spread {
+    pub level: i32,
+    pub evs: [i32; 6],
+    pub ivs: [i32; 6],
+    pub nature: Nature,
+}
+
+impl Default for Spread {
+    fn default() -> Self {
+        Self {
+            level: 100,
+            evs: [0; 6],
+            ivs: [31; 6],
+            nature: Nature::default(),
+        }
+    }
+}
+
+impl Spread {
+    /// hp followed by the five other stats for the given base stats
+    pub fn calc(&self, base: [i32; 6]) -> [i32; 6] {
+        let raw = |i: usize| (2 * base[i] + self.ivs[i] + self.evs[i] / 4) * self.level / 100;
+        let mut out = [0; 6];
+        out[0] = raw(0) + self.level + 10;
+        for (i, stat) in StatId::ALL[..5].iter().enumerate() {
+            out[i + 1] = (raw(i + 1) + 5) * self.nature.modifier(*stat) / 100;
+        }
+        out
+    }
+
+    /// checks the spread is legal: 255 evs per stat and 510 total, ivs up to 31, level 1 to 100
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=100).contains(&self.level) {
+            return Err(format!("level {} is out of range", self.level));
+        }
+        if self.evs.iter().any(|ev| !(0..=255).contains(ev)) {
+            return Err(String::from("evs must be between 0 and 255"));
+        }
+        if self.evs.iter().sum::<i32>() > 510 {
+            return Err(String::from("evs can't total more than 510"));
+        }
+        if self.ivs.iter().any(|iv| !(0..=31).contains(iv)) {
+            return Err(String::from("ivs must be between 0 and 31"));
+        }
+        Ok(())
+    }
+}
+
+/// multiplier for a stat stage as a fraction, 2/2 up to 8/2 and 2/8 at the bottom
+pub fn stage_fraction(stage: i32) -> (i32, i32) {
+    if stage >= 0 {
+        (2 + stage, 2)
+    } else {
+        (2, 2 - stage)
+    }
+}
+
+/// accuracy and evasion use their own table, 3/3 up to 9/3 and 3/9 at the bottom
+pub fn accuracy_fraction(stage: i32) -> (i32, i32) {
+    if stage >= 0 {
+        (3 + stage, 3)
+    } else {
+        (3, 3 - stage)
+    }
 }
 
 impl fmt::Display for Stat {
@@ -130,6 +356,8 @@ impl fmt::Display for StatId {
             StatId::Spa => write!(f, "SPA"),
             StatId::Spd => write!(f, "SPD"),
             StatId::Spe => write!(f, "SPE"),
+            StatId::Acc => write!(f, "ACC"),
+            StatId::Eva => write!(f, "EVA"),
         }
     }
 }
@@ -138,8 +366,8 @@ impl Stat {
     pub fn new(
         value: i32,
         id: StatId,
-        poketype: Rc<RefCell<Poketype>>,
         item: Rc<RefCell<Option<Item>>>,
+        ability: Ability,
         weather: Rc<RefCell<Option<WeatherId>>>,
         status: Rc<RefCell<StatusBlock>>,
     ) -> Self {
@@ -152,43 +380,50 @@ impl Stat {
                 max: 6,
             },
             weather,
-            poketype,
             item,
+            ability,
             status,
         }
     }
 
     pub fn curr(&self) -> i32 {
-        let mut out = match self.stage.data.cmp(&0) {
-            Ordering::Less => self.base / (2 - self.stage.data) * 2,
-            Ordering::Equal => self.base,
-            Ordering::Greater => self.base * ((2 + self.stage.data) / 2),
+        self.with_stage(self.stage.data)
+    }
+
+    pub fn stage(&self) -> i32 {
+        self.stage.data
+    }
+
+    /// the stat's value as if it were at the given stage, used where stages are ignored
+    pub fn with_stage(&self, stage: i32) -> i32 {
+        let (num, den) = match self.id {
+            StatId::Acc | StatId::Eva => accuracy_fraction(stage),
+            _ => stage_fraction(stage),
         };
+        let mut out = self.base * num / den;
+
+        if let Some(item) = *self.item.borrow() {
+            out = item.modify_stat(self.id, out);
+        }
+        out = self.ability.modify_stat(self.id, out);
 
         match self.id {
-            StatId::Spe => {
-                if *self.item.borrow() == Some(Item::ChoiceScarf) {
-                    out = (out * 3) / 2
-                }
-
-                if self.status.borrow().data.contains_key(&Status::Paralyse) {
-                    out /= 4
-                }
-            }
-            StatId::Spd => {
-                if *self.weather.borrow() == Some(WeatherId::Sand) {
-                    out = (out * 3) / 2
-                }
-            }
+            StatId::Spe if self.status.borrow().data.contains_key(&Status::Paralyse) => out /= 4,
+            StatId::Spd if *self.weather.borrow() == Some(WeatherId::Sand) => out = (out * 3) / 2,
             _ => {}
         }
 
         out
     }
 
+    /// returns false if the stage was already at its limit
     pub fn alter(&mut self, diff: i32) -> bool {
         let prev = self.stage.data;
         self.stage += diff;
-        prev == self.stage.data
+        prev != self.stage.data
+    }
+
+    pub fn reset(&mut self) {
+        self.stage.data = 0;
     }
 }
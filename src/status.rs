@@ -1,8 +1,9 @@
 use std::{collections::HashMap, fmt::Display};
 
-use rand::{thread_rng, Rng};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Serialize, Deserialize)]
 pub enum Status {
     Paralyse,
     Burn,
@@ -13,6 +14,8 @@ pub enum Status {
     Flinch,
     Confusion,
     Drowsy,
+    /// fire moves are boosted after absorbing one with Flash Fire
+    FlashFire,
 }
 
 impl Display for Status {
@@ -30,6 +33,7 @@ impl Display for Status {
                 Status::Flinch => String::from("flinched"),
                 Status::Confusion => String::from("confused"),
                 Status::Drowsy => String::from("drowsy"),
+                Status::FlashFire => String::from("flash fire boosted"),
             }
         )
     }
@@ -60,7 +64,7 @@ impl StatusBlock {
         Self::default()
     }
 
-    pub fn add(&mut self, status: &Status) -> bool {
+    pub fn add(&mut self, status: &Status, rng: &mut impl Rng) -> bool {
         if status.is_nv() {
             if self.nv.is_some() {
                 return false;
@@ -69,12 +73,10 @@ impl StatusBlock {
             }
         }
 
-        let mut rand = thread_rng();
-
         if let std::collections::hash_map::Entry::Vacant(e) = self.data.entry(*status) {
             e.insert(match status {
-                Status::Sleep => rand.gen_range(1..=3),
-                Status::Confusion => rand.gen_range(2..5),
+                Status::Sleep => rng.gen_range(1..=3),
+                Status::Confusion => rng.gen_range(2..5),
                 Status::Drowsy => 2,
                 _ => 0,
             });
@@ -94,6 +96,23 @@ impl StatusBlock {
         }
     }
 
+    /// what switching out clears: every volatile status goes and toxic damage starts over,
+    /// sleep keeps counting down where it left off
+    pub fn clear_volatile(&mut self) {
+        self.data.retain(|status, _| status.is_nv());
+        if let Some(counter) = self.data.get_mut(&Status::Toxic) {
+            *counter = 0;
+        }
+    }
+
+    /// removes the status, clearing the non volatile slot if it was held there
+    pub fn remove(&mut self, status: &Status) {
+        self.data.remove(status);
+        if self.nv == Some(*status) {
+            self.nv = None;
+        }
+    }
+
     pub fn increment(&mut self, status: &Status) {
         if let Some(data) = self.data.get_mut(status) {
             match status {
@@ -1,11 +1,122 @@
 use std::fmt::Display;
 
-#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+use rand::Rng;
+use rand_chacha::ChaCha12Rng;
+
+use crate::damage::{DamageCalc, Modifier};
+use crate::dex::to_id;
+use crate::game::{Damage, Effect, WeatherId};
+use crate::moves::{Move, Mtype};
+use crate::pokemon::Pokemon;
+use crate::poketype::Type;
+use crate::selvec::PlayerId;
+use crate::stat::StatId;
+use crate::status::Status;
+use serde::{Deserialize, Serialize};
+
+/// Hooks an ability or item runs at fixed points of the battle. Effects returned from a hook
+/// are applied as if the holder's side were `PlayerId::Active`, apart from the hit hooks where
+/// the holder is the target of the move being used and so `PlayerId::Inactive`.
+pub trait Trigger {
+    /// the holder has just entered the field
+    fn on_switch_in(&self) -> Vec<Effect> {
+        Vec::new()
+    }
+
+    /// the holder is about to be withdrawn
+    fn on_switch_out(&self) -> Vec<Effect> {
+        Vec::new()
+    }
+
+    /// the holder is attacking, modifiers go into `calc` and the returned effects happen
+    /// after the hit
+    fn on_attack(&self, _move: &Move, _calc: &mut DamageCalc) -> Vec<Effect> {
+        Vec::new()
+    }
+
+    /// the holder is being attacked, modifiers go into `calc` and the returned effects happen
+    /// before the hit
+    fn on_defend(&self, _move: &Move, _calc: &mut DamageCalc) -> Vec<Effect> {
+        Vec::new()
+    }
+
+    /// the holder is about to take `damage` from an attack, return.0 is the damage it takes
+    /// instead
+    fn on_damage_taken(
+        &self,
+        _holder: PlayerId,
+        _mon: &Pokemon,
+        damage: i32,
+    ) -> (i32, Vec<Effect>) {
+        (damage, Vec::new())
+    }
+
+    /// the holder's hp, status or stages just changed
+    fn on_update(&self, _holder: PlayerId, _mon: &Pokemon) -> Vec<Effect> {
+        Vec::new()
+    }
+
+    /// a move has finished, after every one of its effects was applied
+    fn on_move_end(&self, _holder: PlayerId, _mon: &Pokemon) -> Vec<Effect> {
+        Vec::new()
+    }
+
+    /// the holder is targeted by a move, if return.1 the move is blocked
+    fn on_before_hit(&self, _move: &Move) -> (Vec<Effect>, bool) {
+        (Vec::new(), false)
+    }
+
+    /// the holder was hit by a damaging move
+    fn on_after_hit(&self, _move: &Move, _rng: &mut ChaCha12Rng) -> Vec<Effect> {
+        Vec::new()
+    }
+
+    /// end of turn
+    fn on_residual(&self, _mon: &Pokemon) -> Vec<Effect> {
+        Vec::new()
+    }
+
+    /// the holder's stat after stages
+    fn modify_stat(&self, _stat: StatId, value: i32) -> i32 {
+        value
+    }
+
+    /// chance of the holder's moves applying their secondary effects
+    fn modify_chance(&self, chance: f32) -> f32 {
+        chance
+    }
+
+    /// the holder can't be given `status`
+    fn blocks_status(&self, _status: Status) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
 pub enum Item {
     #[default]
     Leftovers,
     ChoiceScarf,
     ToxicOrb,
+    ScopeLens,
+    RazorClaw,
+    RockyHelmet,
+    LifeOrb,
+    ChoiceBand,
+    ChoiceSpecs,
+    ExpertBelt,
+    MuscleBand,
+    WiseGlasses,
+    /// boosts moves of its type
+    Plate(Type),
+    FocusSash,
+    SitrusBerry,
+    LumBerry,
+    /// raises the stat at a quarter hp, there is one for each of [`PINCH_STATS`]
+    PinchBerry(StatId),
+    /// halves a super effective hit of its type, chilan berry takes any normal hit
+    ResistBerry(Type),
+    WhiteHerb,
 }
 
 impl Display for Item {
@@ -17,21 +128,274 @@ impl Display for Item {
                 Item::Leftovers => String::from("Leftovers"),
                 Item::ChoiceScarf => String::from("Choice Scarf"),
                 Item::ToxicOrb => String::from("Toxic Orb"),
+                Item::ScopeLens => String::from("Scope Lens"),
+                Item::RazorClaw => String::from("Razor Claw"),
+                Item::RockyHelmet => String::from("Rocky Helmet"),
+                Item::LifeOrb => String::from("Life Orb"),
+                Item::ChoiceBand => String::from("Choice Band"),
+                Item::ChoiceSpecs => String::from("Choice Specs"),
+                Item::ExpertBelt => String::from("Expert Belt"),
+                Item::MuscleBand => String::from("Muscle Band"),
+                Item::WiseGlasses => String::from("Wise Glasses"),
+                Item::Plate(poke_type) => format!("{} Plate", plate_name(poke_type)),
+                Item::FocusSash => String::from("Focus Sash"),
+                Item::SitrusBerry => String::from("Sitrus Berry"),
+                Item::LumBerry => String::from("Lum Berry"),
+                Item::PinchBerry(stat) => format!("{} Berry", pinch_berry_name(stat)),
+                Item::ResistBerry(poke_type) => {
+                    format!("{} Berry", resist_berry_name(poke_type))
+                }
+                Item::WhiteHerb => String::from("White Herb"),
             }
         )
     }
 }
 
 impl Item {
+    /// every item, plates and berries once per type or stat that has one
+    pub fn all() -> Vec<Item> {
+        let mut out = vec![
+            Item::Leftovers,
+            Item::ChoiceScarf,
+            Item::ToxicOrb,
+            Item::ScopeLens,
+            Item::RazorClaw,
+            Item::RockyHelmet,
+            Item::LifeOrb,
+            Item::ChoiceBand,
+            Item::ChoiceSpecs,
+            Item::ExpertBelt,
+            Item::MuscleBand,
+            Item::WiseGlasses,
+            Item::FocusSash,
+            Item::SitrusBerry,
+            Item::LumBerry,
+            Item::WhiteHerb,
+        ];
+        let types = Type::ALL
+            .into_iter()
+            .filter(|poke_type| *poke_type != Type::Typeless);
+        out.extend(types.clone().map(Item::Plate));
+        out.extend(PINCH_STATS.map(Item::PinchBerry));
+        out.extend(types.map(Item::ResistBerry));
+        out
+    }
+
+    /// the item shown as `name`, items sharing a name resolve to the first of them
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|item| to_id(&item.to_string()) == to_id(name))
+    }
+
     pub fn is_choice(&self) -> bool {
+        matches!(
+            self,
+            Item::ChoiceScarf | Item::ChoiceBand | Item::ChoiceSpecs
+        )
+    }
+
+    pub fn is_berry(&self) -> bool {
+        matches!(
+            self,
+            Item::SitrusBerry | Item::LumBerry | Item::PinchBerry(_) | Item::ResistBerry(_)
+        )
+    }
+
+    pub fn crit_stage(&self) -> i8 {
         match self {
-            Item::ChoiceScarf => true,
-            _ => false,
+            Item::ScopeLens | Item::RazorClaw => 1,
+            _ => 0,
         }
     }
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+fn plate_name(poke_type: &Type) -> &'static str {
+    match poke_type {
+        Type::Fire => "Flame",
+        Type::Water => "Splash",
+        Type::Electric => "Zap",
+        Type::Grass => "Meadow",
+        Type::Ice => "Icicle",
+        Type::Fighting => "Fist",
+        Type::Poison => "Toxic",
+        Type::Ground => "Earth",
+        Type::Flying => "Sky",
+        Type::Psychic => "Mind",
+        Type::Bug => "Insect",
+        Type::Rock => "Stone",
+        Type::Ghost => "Spooky",
+        Type::Dragon => "Draco",
+        Type::Dark => "Dread",
+        Type::Steel => "Iron",
+        Type::Normal => "Blank",
+        Type::Typeless => unreachable!("there is no typeless plate"),
+    }
+}
+
+/// stats a pinch berry can raise
+pub const PINCH_STATS: [StatId; 5] = [
+    StatId::Atk,
+    StatId::Def,
+    StatId::Spa,
+    StatId::Spd,
+    StatId::Spe,
+];
+
+fn pinch_berry_name(stat: &StatId) -> &'static str {
+    match stat {
+        StatId::Atk => "Liechi",
+        StatId::Def => "Ganlon",
+        StatId::Spa => "Petaya",
+        StatId::Spd => "Apicot",
+        StatId::Spe => "Salac",
+        StatId::Acc | StatId::Eva => unreachable!("there is no pinch berry for {}", stat),
+    }
+}
+
+fn resist_berry_name(poke_type: &Type) -> &'static str {
+    match poke_type {
+        Type::Fire => "Occa",
+        Type::Water => "Passho",
+        Type::Electric => "Wacan",
+        Type::Grass => "Rindo",
+        Type::Ice => "Yache",
+        Type::Fighting => "Chople",
+        Type::Poison => "Kebia",
+        Type::Ground => "Shuca",
+        Type::Flying => "Coba",
+        Type::Psychic => "Payapa",
+        Type::Bug => "Tanga",
+        Type::Rock => "Charti",
+        Type::Ghost => "Kasib",
+        Type::Dragon => "Haban",
+        Type::Dark => "Colbur",
+        Type::Steel => "Babiri",
+        Type::Normal => "Chilan",
+        Type::Typeless => unreachable!("there is no typeless resist berry"),
+    }
+}
+
+impl Trigger for Item {
+    fn on_attack(&self, move_: &Move, calc: &mut DamageCalc) -> Vec<Effect> {
+        let physical = move_.damage_type == Mtype::Physical;
+        match self {
+            Item::LifeOrb => {
+                calc.mod2.push(Modifier(13, 10));
+                return vec![
+                    Effect::ShowItem(PlayerId::Active),
+                    Effect::Damage(PlayerId::Active, Damage::Fractional(1, 10)),
+                ];
+            }
+            Item::ChoiceBand if physical => calc.attack_mods.push(Modifier(3, 2)),
+            Item::ChoiceSpecs if !physical => calc.attack_mods.push(Modifier(3, 2)),
+            Item::MuscleBand if physical => calc.power_mods.push(Modifier(11, 10)),
+            Item::WiseGlasses if !physical => calc.power_mods.push(Modifier(11, 10)),
+            Item::Plate(poke_type) if *poke_type == move_.poke_type => {
+                calc.power_mods.push(Modifier(6, 5))
+            }
+            Item::ExpertBelt if calc.effectiveness.iter().product::<f32>() > 1.0 => {
+                calc.mod3.push(Modifier(6, 5))
+            }
+            _ => {}
+        }
+        Vec::new()
+    }
+
+    fn on_defend(&self, move_: &Move, calc: &mut DamageCalc) -> Vec<Effect> {
+        match self {
+            Item::ResistBerry(poke_type)
+                if *poke_type == move_.poke_type
+                    && (*poke_type == Type::Normal
+                        || calc.effectiveness.iter().product::<f32>() > 1.0) =>
+            {
+                calc.mod3.push(Modifier(1, 2));
+                vec![Effect::ConsumeItem(PlayerId::Inactive)]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn on_damage_taken(&self, holder: PlayerId, mon: &Pokemon, damage: i32) -> (i32, Vec<Effect>) {
+        match self {
+            Item::FocusSash if mon.hp.is_max() && damage >= mon.hp.data => {
+                (mon.hp.data - 1, vec![Effect::ConsumeItem(holder)])
+            }
+            _ => (damage, Vec::new()),
+        }
+    }
+
+    fn on_update(&self, holder: PlayerId, mon: &Pokemon) -> Vec<Effect> {
+        let (hp, max) = (mon.hp.data, mon.hp.max);
+        match self {
+            Item::SitrusBerry if hp > 0 && hp * 2 <= max => {
+                vec![Effect::ConsumeItem(holder), Effect::Heal(holder, 4)]
+            }
+            Item::PinchBerry(stat) if hp > 0 && hp * 4 <= max => vec![
+                Effect::ConsumeItem(holder),
+                Effect::AlterStat(holder, *stat, 1),
+            ],
+            Item::LumBerry
+                if mon.status.borrow().nv.is_some()
+                    || mon.status.borrow().data.contains_key(&Status::Confusion) =>
+            {
+                vec![
+                    Effect::ConsumeItem(holder),
+                    Effect::Cure(holder),
+                    Effect::EndStatus(holder, Status::Confusion),
+                ]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn on_move_end(&self, holder: PlayerId, mon: &Pokemon) -> Vec<Effect> {
+        match self {
+            // waits for the whole move so every drop it caused is undone at once
+            Item::WhiteHerb => {
+                let drops: Vec<Effect> = StatId::ALL
+                    .iter()
+                    .filter(|stat| mon.stats[**stat].stage() < 0)
+                    .map(|stat| Effect::AlterStat(holder, *stat, -mon.stats[*stat].stage()))
+                    .collect();
+                if drops.is_empty() {
+                    return drops;
+                }
+                let mut out = vec![Effect::ConsumeItem(holder)];
+                out.extend(drops);
+                out
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn on_after_hit(&self, move_: &Move, _rng: &mut ChaCha12Rng) -> Vec<Effect> {
+        match self {
+            Item::RockyHelmet if move_.contact => vec![
+                Effect::ShowItem(PlayerId::Inactive),
+                Effect::Damage(PlayerId::Active, Damage::Fractional(1, 6)),
+            ],
+            _ => Vec::new(),
+        }
+    }
+
+    fn on_residual(&self, _mon: &Pokemon) -> Vec<Effect> {
+        match self {
+            Item::Leftovers => vec![Effect::Heal(PlayerId::Active, 16)],
+            Item::ToxicOrb => vec![Effect::InflictStatus(PlayerId::Active, Status::Toxic)],
+            _ => Vec::new(),
+        }
+    }
+
+    fn modify_stat(&self, stat: StatId, value: i32) -> i32 {
+        match (self, stat) {
+            (Item::ChoiceScarf, StatId::Spe) => value * 3 / 2,
+            _ => value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Ability {
     #[default]
     SandStream,
@@ -41,6 +405,52 @@ pub enum Ability {
     NaturalCure,
     Pressure,
     Levitate,
+    VoltAbsorb,
+    WaterAbsorb,
+    Static,
+    FlameBody,
+    RoughSkin,
+    EffectSpore,
+    Limber,
+    Insomnia,
+    VitalSpirit,
+    Immunity,
+    WaterVeil,
+    MagmaArmor,
+    OwnTempo,
+    InnerFocus,
+}
+
+impl Ability {
+    pub const ALL: [Ability; 21] = [
+        Ability::SandStream,
+        Ability::SereneGrace,
+        Ability::Flashfire,
+        Ability::Poisonheal,
+        Ability::NaturalCure,
+        Ability::Pressure,
+        Ability::Levitate,
+        Ability::VoltAbsorb,
+        Ability::WaterAbsorb,
+        Ability::Static,
+        Ability::FlameBody,
+        Ability::RoughSkin,
+        Ability::EffectSpore,
+        Ability::Limber,
+        Ability::Insomnia,
+        Ability::VitalSpirit,
+        Ability::Immunity,
+        Ability::WaterVeil,
+        Ability::MagmaArmor,
+        Ability::OwnTempo,
+        Ability::InnerFocus,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|ability| to_id(&ability.to_string()) == to_id(name))
+    }
 }
 
 impl Display for Ability {
@@ -56,7 +466,125 @@ impl Display for Ability {
                 Ability::NaturalCure => String::from("Natural Cure"),
                 Ability::Pressure => String::from("Pressure"),
                 Ability::Levitate => String::from("Levitate"),
+                Ability::VoltAbsorb => String::from("Volt Absorb"),
+                Ability::WaterAbsorb => String::from("Water Absorb"),
+                Ability::Static => String::from("Static"),
+                Ability::FlameBody => String::from("Flame Body"),
+                Ability::RoughSkin => String::from("Rough Skin"),
+                Ability::EffectSpore => String::from("Effect Spore"),
+                Ability::Limber => String::from("Limber"),
+                Ability::Insomnia => String::from("Insomnia"),
+                Ability::VitalSpirit => String::from("Vital Spirit"),
+                Ability::Immunity => String::from("Immunity"),
+                Ability::WaterVeil => String::from("Water Veil"),
+                Ability::MagmaArmor => String::from("Magma Armor"),
+                Ability::OwnTempo => String::from("Own Tempo"),
+                Ability::InnerFocus => String::from("Inner Focus"),
             }
         )
     }
 }
+
+impl Trigger for Ability {
+    fn on_switch_in(&self) -> Vec<Effect> {
+        match self {
+            Ability::SandStream => vec![Effect::SetWeather(WeatherId::Sand)],
+            Ability::Pressure => vec![Effect::ShowAbility(PlayerId::Active)],
+            _ => Vec::new(),
+        }
+    }
+
+    fn on_switch_out(&self) -> Vec<Effect> {
+        match self {
+            Ability::NaturalCure => vec![Effect::Cure(PlayerId::Active)],
+            _ => Vec::new(),
+        }
+    }
+
+    fn on_before_hit(&self, move_: &Move) -> (Vec<Effect>, bool) {
+        match self {
+            Ability::Pressure => (vec![Effect::DrainPP(PlayerId::Active, 1)], false),
+            Ability::Levitate if move_.poke_type == Type::Ground => {
+                (vec![Effect::ShowAbility(PlayerId::Inactive)], true)
+            }
+            Ability::Flashfire if move_.poke_type == Type::Fire => (
+                vec![
+                    Effect::ShowAbility(PlayerId::Inactive),
+                    Effect::InflictStatus(PlayerId::Inactive, Status::FlashFire),
+                ],
+                true,
+            ),
+            Ability::VoltAbsorb if move_.poke_type == Type::Electric => (
+                vec![
+                    Effect::ShowAbility(PlayerId::Inactive),
+                    Effect::Heal(PlayerId::Inactive, 4),
+                ],
+                true,
+            ),
+            Ability::WaterAbsorb if move_.poke_type == Type::Water => (
+                vec![
+                    Effect::ShowAbility(PlayerId::Inactive),
+                    Effect::Heal(PlayerId::Inactive, 4),
+                ],
+                true,
+            ),
+            _ => (Vec::new(), false),
+        }
+    }
+
+    fn on_after_hit(&self, move_: &Move, rng: &mut ChaCha12Rng) -> Vec<Effect> {
+        if !move_.contact {
+            return Vec::new();
+        }
+        let status = match self {
+            Ability::Static if rng.gen::<f32>() < 0.3 => Some(Status::Paralyse),
+            Ability::FlameBody if rng.gen::<f32>() < 0.3 => Some(Status::Burn),
+            Ability::EffectSpore => match rng.gen_range(0..10) {
+                0 => Some(Status::Sleep),
+                1 => Some(Status::Paralyse),
+                2 => Some(Status::Poison),
+                _ => None,
+            },
+            Ability::RoughSkin => {
+                return vec![
+                    Effect::ShowAbility(PlayerId::Inactive),
+                    Effect::Damage(PlayerId::Active, Damage::Fractional(1, 8)),
+                ]
+            }
+            _ => None,
+        };
+        status.map_or(Vec::new(), |status| {
+            vec![Effect::InflictStatus(PlayerId::Active, status)]
+        })
+    }
+
+    fn on_residual(&self, mon: &Pokemon) -> Vec<Effect> {
+        let status = mon.status.borrow().nv;
+        match self {
+            Ability::Poisonheal if matches!(status, Some(Status::Poison | Status::Toxic)) => {
+                vec![Effect::Heal(PlayerId::Active, 8)]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn modify_chance(&self, chance: f32) -> f32 {
+        match self {
+            Ability::SereneGrace => chance * 2.0,
+            _ => chance,
+        }
+    }
+
+    fn blocks_status(&self, status: Status) -> bool {
+        matches!(
+            (self, status),
+            (Ability::Limber, Status::Paralyse)
+                | (Ability::Insomnia | Ability::VitalSpirit, Status::Sleep)
+                | (Ability::Immunity, Status::Poison | Status::Toxic)
+                | (Ability::WaterVeil, Status::Burn)
+                | (Ability::MagmaArmor, Status::Freeze)
+                | (Ability::OwnTempo, Status::Confusion)
+                | (Ability::InnerFocus, Status::Flinch)
+        )
+    }
+}
@@ -32,8 +32,6 @@ pub enum IMode {
 impl App {
     /// Renders the user interface widgets.
     pub fn render(&mut self, frame: &mut Frame) {
-        let border = BorderType::Plain;
-
         let [_, main_bar, _] = Layout::vertical([
             Constraint::Fill(1),
             Constraint::Length(20),
@@ -44,9 +42,9 @@ impl App {
         let [_, pklist_1, mvlist_1, middle, mvlist_2, pklist_2, _] = Layout::horizontal([
             Constraint::Fill(1),
             Constraint::Length(25),
-            Constraint::Length(25),
+            Constraint::Length(28),
             Constraint::Length(30),
-            Constraint::Length(25),
+            Constraint::Length(28),
             Constraint::Length(25),
             Constraint::Fill(1),
         ])
@@ -160,18 +158,24 @@ impl App {
                     .data
                     .iter()
                     .enumerate()
-                    .map(
-                        |(k, move_)| match self.games.players[i].roster[selection].moves.active {
+                    .map(|(k, move_)| {
+                        let move_str = format!(
+                            "{:16} {:>2}/{:<2}",
+                            move_.id.to_string(),
+                            move_.pp,
+                            move_.pp.max
+                        );
+                        match self.games.players[i].roster[selection].choice_lock() {
                             Some(active) => {
                                 if active == k {
-                                    ListItem::from(format!("{}", move_.id)).fg(Color::Magenta)
+                                    ListItem::from(move_str).fg(Color::Magenta)
                                 } else {
-                                    ListItem::from(format!("{}", move_.id))
+                                    ListItem::from(move_str)
                                 }
                             }
-                            None => ListItem::from(format!("{}", move_.id)),
-                        },
-                    )
+                            None => ListItem::from(move_str),
+                        }
+                    })
                     .collect()
             } else {
                 vec![]
@@ -191,7 +195,7 @@ impl App {
                     self.games.players[i].roster[self.ui.pokelist[i].selected().unwrap()].moves[k]
                 )
             } else {
-                format!("No selection")
+                "No selection".to_string()
             };
 
             let block = Block::new()
@@ -215,13 +219,16 @@ impl App {
 
         if log.len() > 22 + self.ui.log_idx {
             for i in 0..22 {
-                disp_log.push_str(&format!("{}\n", log[i + self.ui.log_idx]));
+                disp_log.push_str(&format!(
+                    "{}\n",
+                    log[i + self.ui.log_idx].render(&self.games.players.data)
+                ));
             }
         } else {
             for ele in log[self.ui.log_idx..].iter() {
-                disp_log.push_str(&format!("{}\n", ele));
+                disp_log.push_str(&format!("{}\n", ele.render(&self.games.players.data)));
             }
-            for i in 0..22 - log[self.ui.log_idx..].len() {
+            for _ in 0..22 - log[self.ui.log_idx..].len() {
                 disp_log.push_str(&String::from("\n"))
             }
         }
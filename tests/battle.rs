@@ -0,0 +1,663 @@
+use pkml::{
+    battle_event::BattleEvent,
+    game::{Game, GameResult, GameState, MoveSelection, WeatherId},
+    moves::MoveId,
+    paste::parse_team,
+    pokemon::Pokemon,
+    poketype::{Poketype, Type},
+    preset::PokeId,
+    sim::run_game,
+    stat::StatId,
+    status::Status,
+    trigger::Ability,
+};
+
+/// Heatran explodes on a Jirachi left at 1 hp, Jirachi is faster and only uses Refresh
+fn explosion_game(heatran: &str, jirachi: &str) -> Game {
+    let teams = [parse_team(heatran).unwrap(), parse_team(jirachi).unwrap()];
+    let mut game = Game::with_teams(0, teams);
+    game.players[1].roster.active_mut().unwrap().hp.data = 1;
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+    game
+}
+
+#[test]
+fn explosion_ties_last_mons() {
+    let game = explosion_game("Heatran\n- Explosion", "Jirachi\n- Refresh");
+
+    assert_eq!(game.state, GameState::Completed(GameResult::Tie));
+    assert_eq!(game.players[0].roster.dead, 0);
+    assert_eq!(game.players[1].roster.dead, 0);
+}
+
+#[test]
+fn double_ko_replacements_enter_together() {
+    let mut game = explosion_game(
+        "Heatran\n- Explosion\n\nTyranitar\n- Crunch",
+        "Jirachi\n- Refresh\n\nZapdos\n- Roost",
+    );
+
+    // the bot's replacement stays hidden until the human has picked
+    assert_eq!(game.state, GameState::AwaitingSwitch);
+    assert_eq!(game.players.active, Some(0));
+    assert!(!game.players[1].has_active());
+
+    let replacement = game.players[0].list_valid_inputs(&game.state)[0];
+    game.players[0].inputs.push(replacement);
+    game.execute_turn();
+
+    assert_eq!(game.state, GameState::TurnStart);
+    let log = game.log.last().unwrap();
+    let entries: Vec<usize> = (0..log.len())
+        .filter(|&i| matches!(log[i], BattleEvent::Switch { .. }))
+        .collect();
+    let sand = log
+        .iter()
+        .position(|event| *event == BattleEvent::WeatherSet(WeatherId::Sand))
+        .expect("Tyranitar's Sand Stream never activated");
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|&i| i < sand));
+}
+
+#[test]
+fn same_seed_same_log() {
+    for seed in 0..20 {
+        let game = run_game(seed);
+        assert!(game.turn_count > 0);
+        assert_eq!(run_game(seed).log, game.log, "seed {} diverged", seed);
+    }
+}
+
+#[test]
+fn faster_lead_enters_first() {
+    let teams = [
+        parse_team("Tyranitar\n- Crunch").unwrap(),
+        parse_team("Zapdos\n- Roost").unwrap(),
+    ];
+    let game = Game::with_teams(0, teams);
+
+    let log = &game.log[0];
+    let pressure = log
+        .iter()
+        .position(|event| matches!(event, BattleEvent::AbilityActivated { player: 1, .. }))
+        .expect("Zapdos' Pressure never activated");
+    let sand = log
+        .iter()
+        .position(|event| *event == BattleEvent::WeatherSet(WeatherId::Sand))
+        .expect("Tyranitar's Sand Stream never activated");
+    assert!(pressure < sand);
+}
+
+#[test]
+fn ability_messages() {
+    let game = Game::with_seed(0);
+    let render = |ability| {
+        BattleEvent::AbilityActivated {
+            player: 1,
+            mon: PokeId::from_name("Heatran").unwrap(),
+            ability,
+        }
+        .render(&game.players.data)
+    };
+
+    assert_eq!(
+        render(Ability::Flashfire),
+        "Bot's Heatran's Flash Fire activated!"
+    );
+    assert_eq!(
+        render(Ability::WaterAbsorb),
+        "Bot's Heatran absorbed the attack with Water Absorb!"
+    );
+    assert_eq!(
+        render(Ability::SandStream),
+        "Bot's Heatran's Sand Stream activated!"
+    );
+}
+
+#[test]
+fn fainted_u_turn_user_replaced_at_turn_end() {
+    let teams = [
+        parse_team("Starmie\n- U-turn\n\nTyranitar\n- Crunch\n\nJirachi\n- Refresh").unwrap(),
+        parse_team("Zapdos @ Rocky Helmet\n- Roost").unwrap(),
+    ];
+    let mut game = Game::with_teams(0, teams);
+    game.players[0].roster.active_mut().unwrap().hp.data = 1;
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+
+    // Zapdos still gets to move before the replacement comes in
+    let log = game.log.last().unwrap();
+    assert!(!log
+        .iter()
+        .any(|event| matches!(event, BattleEvent::SwitchPrompt { .. })));
+    assert!(log
+        .iter()
+        .any(|event| matches!(event, BattleEvent::MoveUsed { player: 1, .. })));
+    assert_eq!(game.state, GameState::AwaitingSwitch);
+
+    game.players[0].inputs.push(MoveSelection::Switch(0));
+    game.execute_turn();
+    assert_eq!(game.state, GameState::TurnStart);
+    assert!(game.players[0].has_active());
+}
+
+#[test]
+fn flash_fire_boost_logged_once() {
+    let teams = [
+        parse_team("Heatran\n- Fire Blast").unwrap(),
+        parse_team("Heatran\n- Fire Blast").unwrap(),
+    ];
+    let mut game = Game::with_teams(0, teams);
+    let boosts = |log: &[BattleEvent]| {
+        let count = |f: fn(&BattleEvent) -> bool| log.iter().filter(|event| f(event)).count();
+        (
+            count(|event| matches!(event, BattleEvent::AbilityActivated { .. })),
+            count(|event| {
+                matches!(
+                    event,
+                    BattleEvent::StatusInflicted {
+                        status: Status::FlashFire,
+                        ..
+                    }
+                )
+            }),
+        )
+    };
+
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+    assert_eq!(boosts(game.log.last().unwrap()), (2, 2));
+
+    // already boosted, the ability still shows but the boost isn't repeated
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+    assert_eq!(boosts(game.log.last().unwrap()), (2, 0));
+}
+
+#[test]
+fn stat_limits_logged() {
+    let teams = [
+        parse_team("Tyranitar\n- Superpower").unwrap(),
+        parse_team("Jirachi\n- Refresh").unwrap(),
+    ];
+    let mut game = Game::with_teams(0, teams);
+    let user = game.players[0].roster.active_mut().unwrap();
+    user.stats[StatId::Atk].alter(-6);
+    user.stats[StatId::Def].alter(-5);
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+
+    let log = game.log.last().unwrap();
+    assert!(log.contains(&BattleEvent::StatUnchanged {
+        player: 0,
+        mon: PokeId::from_name("Tyranitar").unwrap(),
+        stat: StatId::Atk,
+        raise: false,
+    }));
+    assert!(log.contains(&BattleEvent::StatChanged {
+        player: 0,
+        mon: PokeId::from_name("Tyranitar").unwrap(),
+        stat: StatId::Def,
+        stages: -1,
+    }));
+    assert!(!log.iter().any(|event| matches!(
+        event,
+        BattleEvent::StatChanged {
+            stat: StatId::Atk,
+            ..
+        }
+    )));
+}
+
+#[test]
+fn thunder_wave_fails_on_ground_types() {
+    let teams = [
+        parse_team("Jirachi\n- Thunder Wave").unwrap(),
+        parse_team("Zapdos\n- Roost").unwrap(),
+    ];
+    let mut game = Game::with_teams(0, teams);
+    let zapdos = game.players[1].roster.active().unwrap();
+    *zapdos.poketype.borrow_mut() = Poketype::Mono(Type::Ground);
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+
+    let turn = game.log.last().unwrap();
+    assert!(turn
+        .iter()
+        .any(|event| matches!(event, BattleEvent::NoEffect { player: 1, .. })));
+    let zapdos = game.players[1].roster.active().unwrap();
+    assert_eq!(zapdos.status.borrow().nv, None);
+}
+
+#[test]
+fn hypnosis_ignores_the_type_chart() {
+    // Psychic can't hit Dark types, but Hypnosis still lands whenever it passes the accuracy roll
+    let mut slept = 0;
+    for seed in 0..8 {
+        let teams = [
+            parse_team("Jirachi\n- Hypnosis").unwrap(),
+            parse_team("Tyranitar\n- Crunch").unwrap(),
+        ];
+        let mut game = Game::with_teams(seed, teams);
+        game.players[0].inputs.push(MoveSelection::Move(0));
+        game.execute_turn();
+
+        let turn = game.log.last().unwrap();
+        assert!(!turn
+            .iter()
+            .any(|event| matches!(event, BattleEvent::NoEffect { .. })));
+        let tyranitar = game.players[1].roster.active().unwrap();
+        if tyranitar.status.borrow().nv == Some(Status::Sleep) {
+            slept += 1;
+        }
+    }
+    assert!(slept > 0);
+}
+
+#[test]
+fn missed_moves_deal_no_damage() {
+    let (mut hits, mut misses) = (0, 0);
+    for seed in 0..16 {
+        let teams = [
+            parse_team("Starmie\n- Hydro Pump").unwrap(),
+            parse_team("Jirachi\n- Refresh").unwrap(),
+        ];
+        let mut game = Game::with_teams(seed, teams);
+        // Hydro Pump hits 80% of the time, at -6 accuracy only about a quarter of the time
+        game.players[0].roster.active_mut().unwrap().stats[StatId::Acc].alter(-6);
+        game.players[0].inputs.push(MoveSelection::Move(0));
+        game.execute_turn();
+
+        let turn = game.log.last().unwrap();
+        let missed = turn
+            .iter()
+            .any(|event| matches!(event, BattleEvent::MoveMissed { player: 0, .. }));
+        let damaged = turn
+            .iter()
+            .any(|event| matches!(event, BattleEvent::Damage { player: 1, .. }));
+        assert_ne!(missed, damaged, "seed {}", seed);
+        if missed {
+            let jirachi = game.players[1].roster.active().unwrap();
+            assert_eq!(jirachi.hp.data, jirachi.hp.max);
+            misses += 1;
+        } else {
+            hits += 1;
+        }
+    }
+    assert!(hits > 0 && misses > hits);
+}
+
+#[test]
+fn moves_without_accuracy_never_miss() {
+    for seed in 0..16 {
+        let teams = [
+            parse_team("Starmie\n- Yawn").unwrap(),
+            parse_team("Jirachi\n- Refresh").unwrap(),
+        ];
+        let mut game = Game::with_teams(seed, teams);
+        game.players[0].roster.active_mut().unwrap().stats[StatId::Acc].alter(-6);
+        game.players[1].roster.active_mut().unwrap().stats[StatId::Eva].alter(6);
+        game.players[0].inputs.push(MoveSelection::Move(0));
+        game.execute_turn();
+
+        let turn = game.log.last().unwrap();
+        assert!(
+            !turn
+                .iter()
+                .any(|event| matches!(event, BattleEvent::MoveMissed { .. })),
+            "seed {}",
+            seed
+        );
+    }
+}
+
+#[test]
+fn exhausted_moves_force_struggle() {
+    let teams = [
+        parse_team("Tyranitar\n- Crunch\n- Stone Edge").unwrap(),
+        parse_team("Jirachi\n- Refresh").unwrap(),
+    ];
+    let mut game = Game::with_teams(0, teams);
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+
+    let tyranitar = game.players[0].roster.active_mut().unwrap();
+    let crunch = &mut tyranitar.moves.data[0];
+    assert_eq!(crunch.pp.data, crunch.pp.max - 1);
+    crunch.pp.data = 0;
+    assert_eq!(
+        game.players[0].list_valid_inputs(&game.state),
+        vec![MoveSelection::Move(1)]
+    );
+
+    game.players[0].roster.active_mut().unwrap().moves.data[1]
+        .pp
+        .data = 0;
+    assert_eq!(
+        game.players[0].list_valid_inputs(&game.state),
+        vec![MoveSelection::Struggle]
+    );
+    game.players[0].inputs.push(MoveSelection::Struggle);
+    game.execute_turn();
+
+    let turn = game.log.last().unwrap();
+    let struggle = MoveId::from_name("Struggle").unwrap();
+    assert!(turn.iter().any(|event| matches!(
+        event,
+        BattleEvent::MoveUsed { player: 0, move_id, .. } if *move_id == struggle
+    )));
+    // the recoil hits Tyranitar itself
+    assert!(turn
+        .iter()
+        .any(|event| matches!(event, BattleEvent::Damage { player: 0, .. })));
+}
+
+#[test]
+fn critical_hits_ignore_attack_drops() {
+    let (mut crits, mut hits) = (Vec::new(), Vec::new());
+    for seed in 0..40 {
+        // Stone Edge with a Scope Lens crits a quarter of the time
+        let teams = [
+            parse_team("Tyranitar @ Scope Lens\n- Stone Edge").unwrap(),
+            parse_team("Jirachi\n- Refresh").unwrap(),
+        ];
+        let mut game = Game::with_teams(seed, teams);
+        game.players[0].roster.active_mut().unwrap().stats[StatId::Atk].alter(-6);
+        game.players[0].inputs.push(MoveSelection::Move(0));
+        game.execute_turn();
+
+        let turn = game.log.last().unwrap();
+        let Some(amount) = turn.iter().find_map(|event| match event {
+            BattleEvent::Damage {
+                player: 1, amount, ..
+            } => Some(*amount),
+            _ => None,
+        }) else {
+            continue;
+        };
+        if turn.contains(&BattleEvent::CriticalHit) {
+            crits.push(amount);
+        } else {
+            hits.push(amount);
+        }
+    }
+
+    assert!(!crits.is_empty() && !hits.is_empty());
+    assert!(crits.len() < hits.len());
+    // at -6 a normal hit does a quarter of the damage, a crit ignores the drop and doubles
+    assert!(crits.iter().min() > hits.iter().max());
+}
+
+#[test]
+fn choice_lock_lasts_until_switching_out() {
+    let teams = [
+        parse_team("Starmie @ Choice Scarf\n- Hydro Pump\n- Thunderbolt\n\nJirachi\n- Refresh")
+            .unwrap(),
+        parse_team("Zapdos\n- Roost").unwrap(),
+    ];
+    let mut game = Game::with_teams(0, teams);
+    game.players[0].inputs.push(MoveSelection::Move(1));
+    game.execute_turn();
+
+    assert_eq!(
+        game.players[0].list_valid_inputs(&game.state),
+        vec![MoveSelection::Switch(1), MoveSelection::Move(1)]
+    );
+
+    game.players[0].inputs.push(MoveSelection::Switch(1));
+    game.execute_turn();
+    let starmie = game.players[0]
+        .roster
+        .data
+        .iter()
+        .find(|mon| mon.id == PokeId::from_name("Starmie").unwrap())
+        .unwrap();
+    assert_eq!(starmie.choice_lock(), None);
+}
+
+/// Tyranitar uses Crunch on a Jirachi that only uses Refresh
+fn crunch_game(item: &str) -> Game {
+    let teams = [
+        parse_team(&format!("Tyranitar{}\n- Crunch", item)).unwrap(),
+        parse_team("Jirachi\n- Refresh").unwrap(),
+    ];
+    let mut game = Game::with_teams(0, teams);
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+    game
+}
+
+/// the damage the player's mon took during the last turn
+fn damage_taken(game: &Game, target: usize) -> i32 {
+    game.log
+        .last()
+        .unwrap()
+        .iter()
+        .filter_map(|event| match *event {
+            BattleEvent::Damage { player, amount, .. } if player == target => Some(amount),
+            _ => None,
+        })
+        .sum()
+}
+
+#[test]
+fn damage_items_boost_through_the_calc() {
+    let plain = crunch_game("");
+    let orb = crunch_game(" @ Life Orb");
+    let band = crunch_game(" @ Choice Band");
+    let belt = crunch_game(" @ Expert Belt");
+
+    assert!(damage_taken(&orb, 1) > damage_taken(&plain, 1));
+    assert!(damage_taken(&band, 1) > damage_taken(&orb, 1));
+    // Crunch isn't super effective on Jirachi
+    assert_eq!(damage_taken(&belt, 1), damage_taken(&plain, 1));
+
+    // only Life Orb costs the user hp
+    assert_eq!(damage_taken(&plain, 0), 0);
+    let tyranitar = orb.players[0].roster.active().unwrap();
+    assert_eq!(damage_taken(&orb, 0), tyranitar.hp.max / 10);
+}
+
+/// a confused Tyranitar at the given hp tries to Crunch a Jirachi that only uses Refresh
+fn confused_game(seed: u64, hp: Option<i32>) -> Game {
+    let teams = [
+        parse_team("Tyranitar\n- Crunch\n\nBreloom\n- Spore").unwrap(),
+        parse_team("Jirachi\n- Refresh").unwrap(),
+    ];
+    let mut game = Game::with_teams(seed, teams);
+    let tyranitar = game.players[0].roster.active_mut().unwrap();
+    tyranitar
+        .status
+        .borrow_mut()
+        .data
+        .insert(Status::Confusion, 4);
+    if let Some(hp) = hp {
+        tyranitar.hp.data = hp;
+    }
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+    game
+}
+
+fn hit_itself(game: &Game) -> bool {
+    game.log
+        .last()
+        .unwrap()
+        .contains(&BattleEvent::MoveSkipped {
+            mon: PokeId::from_name("Tyranitar").unwrap(),
+            cause: Status::Confusion,
+        })
+}
+
+#[test]
+fn confusion_self_hits_deal_damage() {
+    let games: Vec<Game> = (0..16).map(|seed| confused_game(seed, None)).collect();
+    assert!(games.iter().any(hit_itself));
+    for game in games.iter().filter(|game| hit_itself(game)) {
+        assert!(damage_taken(game, 0) > 0);
+        assert_eq!(damage_taken(game, 1), 0);
+        let tyranitar = game.players[0].roster.active().unwrap();
+        assert_eq!(tyranitar.hp.data, tyranitar.hp.max - damage_taken(game, 0));
+    }
+
+    let game = (0..16)
+        .map(|seed| confused_game(seed, Some(1)))
+        .find(hit_itself)
+        .unwrap();
+    assert!(game
+        .log
+        .last()
+        .unwrap()
+        .iter()
+        .any(|event| matches!(event, BattleEvent::Faint { player: 0, .. })));
+    assert_eq!(game.state, GameState::AwaitingSwitch);
+}
+
+fn freeze(game: &mut Game, player: usize) {
+    let mon = game.players[player].roster.active_mut().unwrap();
+    let mut status = mon.status.borrow_mut();
+    status.nv = Some(Status::Freeze);
+    status.data.insert(Status::Freeze, 0);
+}
+
+#[test]
+fn ice_moves_freeze() {
+    let frozen = (0..64)
+        .filter(|&seed| {
+            let teams = [
+                parse_team("Starmie\n- Ice Beam").unwrap(),
+                parse_team("Zapdos\n- Roost").unwrap(),
+            ];
+            let mut game = Game::with_teams(seed, teams);
+            game.players[0].inputs.push(MoveSelection::Move(0));
+            game.execute_turn();
+            game.log.last().unwrap().iter().any(|event| {
+                matches!(
+                    event,
+                    BattleEvent::StatusInflicted {
+                        player: 1,
+                        status: Status::Freeze,
+                        ..
+                    }
+                )
+            })
+        })
+        .count();
+    assert!(frozen > 0);
+}
+
+#[test]
+fn fire_moves_thaw_the_user_and_target() {
+    let heatran = PokeId::from_name("Heatran").unwrap();
+    let teams = [
+        parse_team("Heatran\n- Fire Blast").unwrap(),
+        parse_team("Jirachi\n- Refresh").unwrap(),
+    ];
+    let mut game = Game::with_teams(0, teams);
+    freeze(&mut game, 0);
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+    let turn = game.log.last().unwrap();
+    assert!(turn.contains(&BattleEvent::StatusEnded {
+        mon: heatran,
+        status: Status::Freeze,
+    }));
+    assert!(turn
+        .iter()
+        .any(|event| matches!(event, BattleEvent::MoveUsed { player: 0, .. })));
+
+    // Tyranitar is slower, so it thaws before its own turn comes around
+    let teams = [
+        parse_team("Jirachi\n- Fire Punch").unwrap(),
+        parse_team("Tyranitar\n- Crunch").unwrap(),
+    ];
+    let mut game = Game::with_teams(0, teams);
+    freeze(&mut game, 1);
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+    let turn = game.log.last().unwrap();
+    assert!(turn
+        .iter()
+        .any(|event| matches!(event, BattleEvent::MoveUsed { player: 1, .. })));
+    let tyranitar = game.players[1].roster.active().unwrap();
+    assert_ne!(tyranitar.status.borrow().nv, Some(Status::Freeze));
+}
+
+#[test]
+fn yawn_puts_the_target_to_sleep_a_turn_later() {
+    let teams = [
+        parse_team("Starmie\n- Yawn\n- Hydro Pump").unwrap(),
+        parse_team("Zapdos\n- Roost").unwrap(),
+    ];
+    let mut game = Game::with_teams(0, teams);
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+    {
+        let status = game.players[1].roster.active().unwrap().status.borrow();
+        assert!(status.data.contains_key(&Status::Drowsy));
+        assert_eq!(status.nv, None);
+    }
+
+    game.players[0].inputs.push(MoveSelection::Move(1));
+    game.execute_turn();
+    let status = game.players[1].roster.active().unwrap().status.borrow();
+    assert_eq!(status.nv, Some(Status::Sleep));
+    assert!(!status.data.contains_key(&Status::Drowsy));
+}
+
+/// sets up Tyranitar's status, then switches it out for Jirachi
+fn switch_out_game(statuses: &[(Status, i8)]) -> Game {
+    let teams = [
+        parse_team("Tyranitar\n- Crunch\n\nJirachi\n- Refresh").unwrap(),
+        parse_team("Zapdos\n- Roost").unwrap(),
+    ];
+    let mut game = Game::with_teams(0, teams);
+    let tyranitar = game.players[0].roster.active_mut().unwrap();
+    tyranitar.stats[StatId::Atk].alter(2);
+    tyranitar.stats[StatId::Spe].alter(-1);
+    let mut status = tyranitar.status.borrow_mut();
+    for &(id, counter) in statuses {
+        if id.is_nv() {
+            status.nv = Some(id);
+        }
+        status.data.insert(id, counter);
+    }
+    drop(status);
+    game.players[0].inputs.push(MoveSelection::Switch(1));
+    game.execute_turn();
+    game
+}
+
+fn benched_tyranitar(game: &Game) -> &Pokemon {
+    game.players[0]
+        .roster
+        .data
+        .iter()
+        .find(|mon| mon.id == PokeId::from_name("Tyranitar").unwrap())
+        .unwrap()
+}
+
+#[test]
+fn switching_out_clears_volatile_state() {
+    let game = switch_out_game(&[
+        (Status::Toxic, 3),
+        (Status::Confusion, 3),
+        (Status::Drowsy, 1),
+    ]);
+    let tyranitar = benched_tyranitar(&game);
+    assert!(StatId::ALL
+        .into_iter()
+        .all(|stat| tyranitar.stats[stat].stage() == 0));
+    let status = tyranitar.status.borrow();
+    assert_eq!(status.nv, Some(Status::Toxic));
+    assert_eq!(status.data.get(&Status::Toxic), Some(&0));
+    assert!(!status.data.contains_key(&Status::Confusion));
+    assert!(!status.data.contains_key(&Status::Drowsy));
+
+    // sleep keeps counting down from where it was
+    let game = switch_out_game(&[(Status::Sleep, 2)]);
+    let status = benched_tyranitar(&game).status.borrow();
+    assert_eq!(status.nv, Some(Status::Sleep));
+    assert_eq!(status.data.get(&Status::Sleep), Some(&2));
+}
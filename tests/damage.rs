@@ -0,0 +1,128 @@
+use pkml::damage::{DamageCalc, Modifier};
+
+// Glaceon (Lv. 75, 123 Atk) Ice Fang into Garchomp (163 Def), the worked example from Bulbapedia
+#[test]
+fn bulbapedia_example() {
+    let calc = DamageCalc {
+        level: 75,
+        base_power: 65,
+        attack: 123,
+        defense: 163,
+        stab: true,
+        effectiveness: vec![2.0, 2.0],
+        ..Default::default()
+    };
+    assert_eq!(calc.range(), (168, 196));
+}
+
+// preset Tyranitar (367 Atk) Stone Edge into preset Zapdos (288 Def)
+#[test]
+fn super_effective_stab() {
+    let calc = DamageCalc {
+        base_power: 120,
+        attack: 367,
+        defense: 288,
+        stab: true,
+        effectiveness: vec![1.0, 2.0],
+        ..Default::default()
+    };
+    assert_eq!(calc.range(), (330, 390));
+}
+
+#[test]
+fn crit_doubles_after_base() {
+    let calc = DamageCalc {
+        base_power: 120,
+        attack: 367,
+        defense: 288,
+        crit: true,
+        stab: true,
+        effectiveness: vec![1.0, 2.0],
+        ..Default::default()
+    };
+    assert_eq!(calc.range(), (662, 780));
+}
+
+#[test]
+fn life_orb() {
+    let calc = DamageCalc {
+        base_power: 120,
+        attack: 367,
+        defense: 288,
+        mod2: vec![Modifier(13, 10)],
+        stab: true,
+        effectiveness: vec![1.0, 2.0],
+        ..Default::default()
+    };
+    assert_eq!(calc.calc(DamageCalc::MAX_ROLL), 506);
+}
+
+// preset Starmie (299 SpA) Hydro Pump into preset Heatran (248 SpD) in rain
+#[test]
+fn rain_boost() {
+    let calc = DamageCalc {
+        base_power: 120,
+        attack: 299,
+        defense: 248,
+        weather: Modifier(3, 2),
+        stab: true,
+        effectiveness: vec![2.0, 1.0],
+        ..Default::default()
+    };
+    assert_eq!(calc.range(), (464, 548));
+}
+
+// burned preset Tyranitar (367 Atk) Crunch into preset Starmie (207 Def)
+#[test]
+fn burn_halves_before_adding_two() {
+    let calc = DamageCalc {
+        base_power: 80,
+        attack: 367,
+        defense: 207,
+        burn: true,
+        stab: true,
+        effectiveness: vec![1.0, 2.0],
+        ..Default::default()
+    };
+    assert_eq!(calc.range(), (152, 182));
+}
+
+#[test]
+fn type_order_truncation() {
+    let calc = DamageCalc {
+        base_power: 40,
+        attack: 100,
+        defense: 100,
+        effectiveness: vec![0.5, 2.0],
+        ..Default::default()
+    };
+    assert_eq!(calc.calc(DamageCalc::MAX_ROLL), 34);
+
+    let calc = DamageCalc {
+        effectiveness: vec![2.0, 0.5],
+        ..calc
+    };
+    assert_eq!(calc.calc(DamageCalc::MAX_ROLL), 35);
+}
+
+#[test]
+fn immune_and_minimum() {
+    let calc = DamageCalc {
+        base_power: 80,
+        attack: 300,
+        defense: 200,
+        effectiveness: vec![0.0, 2.0],
+        ..Default::default()
+    };
+    assert_eq!(calc.range(), (0, 0));
+
+    let calc = DamageCalc {
+        level: 1,
+        base_power: 10,
+        attack: 1,
+        defense: 500,
+        effectiveness: vec![0.5, 0.5],
+        ..Default::default()
+    };
+    assert_eq!(calc.range(), (1, 1));
+}
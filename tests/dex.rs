@@ -0,0 +1,71 @@
+use std::{env, fs, path::Path};
+
+use pkml::dex::{Dex, DATA_DIR};
+use serde_json::{json, Value};
+
+#[test]
+fn bundled_data_matches_data_dir() {
+    let bundled = Dex::bundled().unwrap();
+    let loaded = Dex::load(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join(DATA_DIR)
+            .as_path(),
+    )
+    .unwrap();
+    assert_eq!(bundled.species.len(), loaded.species.len());
+    assert_eq!(bundled.moves.len(), loaded.moves.len());
+}
+
+/// copies the shipped data with one move edited and tries to load it, tests running in
+/// parallel must each edit a different move
+fn load_edited(name: &str, edit: impl FnOnce(&mut Value)) -> String {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join(DATA_DIR);
+    let dir = env::temp_dir().join(format!("pkml_{}_{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::copy(src.join("species.json"), dir.join("species.json")).unwrap();
+    let mut moves: Value =
+        serde_json::from_str(&fs::read_to_string(src.join("moves.json")).unwrap()).unwrap();
+    let move_ = moves
+        .as_array_mut()
+        .unwrap()
+        .iter_mut()
+        .find(|move_| move_["name"] == name)
+        .unwrap();
+    edit(move_);
+    fs::write(dir.join("moves.json"), moves.to_string()).unwrap();
+
+    let err = Dex::load(&dir).unwrap_err().to_string();
+    fs::remove_dir_all(&dir).unwrap();
+    err
+}
+
+#[test]
+fn zero_divisors_rejected() {
+    let err = load_edited("Roost", |roost| {
+        roost["effects"] = json!([{ "Heal": ["Active", 0] }]);
+    });
+    assert!(err.contains("Roost"), "{}", err);
+    assert!(err.contains("nonzero divisor"), "{}", err);
+
+    let err = load_edited("Stealth Rock", |rock| {
+        rock["effects"] = json!([{ "Damage": ["Inactive", { "Fractional": [1, 0] }] }]);
+    });
+    assert!(err.contains("nonzero divisor"), "{}", err);
+}
+
+#[test]
+fn engine_only_effects_rejected() {
+    let err = load_edited("Refresh", |refresh| {
+        refresh["effects"] = json!([{ "Switch": 7 }]);
+    });
+    assert!(err.contains("engine-only"), "{}", err);
+}
+
+#[test]
+fn status_move_with_base_power_rejected() {
+    let err = load_edited("Thunder Wave", |wave| {
+        wave["base_power"] = json!(40);
+    });
+    assert!(err.contains("Thunder Wave"), "{}", err);
+    assert!(err.contains("status moves"), "{}", err);
+}
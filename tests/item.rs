@@ -0,0 +1,130 @@
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+
+use pkml::{
+    battle_event::BattleEvent,
+    game::{Effect, Game, MoveSelection},
+    paste::parse_team,
+    pokemon::Pokemon,
+    selvec::PlayerId,
+    stat::StatId,
+    status::Status,
+    trigger::{Item, Trigger},
+};
+
+#[test]
+fn item_names_are_unique() {
+    let items = Item::all();
+    let names: HashSet<String> = items.iter().map(Item::to_string).collect();
+    assert_eq!(names.len(), items.len());
+    for item in items {
+        assert_eq!(Item::from_name(&item.to_string()), Some(item));
+    }
+    assert_eq!(Item::from_name("Micle Berry"), None);
+}
+
+#[test]
+fn lum_berry_cures_confusion() {
+    let set = &parse_team("Jirachi @ Lum Berry\n- Refresh").unwrap()[0];
+    let mon = Pokemon::from_set(set, Rc::new(RefCell::new(None)));
+    assert!(Item::LumBerry.on_update(PlayerId::Active, &mon).is_empty());
+
+    mon.status.borrow_mut().data.insert(Status::Confusion, 3);
+    let effects = Item::LumBerry.on_update(PlayerId::Active, &mon);
+    assert!(effects.contains(&Effect::ConsumeItem(PlayerId::Active)));
+    assert!(effects.contains(&Effect::EndStatus(PlayerId::Active, Status::Confusion)));
+}
+
+#[test]
+fn white_herb_undoes_every_drop_of_a_move() {
+    let teams = [
+        parse_team("Tyranitar @ White Herb\n- Superpower").unwrap(),
+        parse_team("Jirachi\n- Refresh").unwrap(),
+    ];
+    let mut game = Game::with_teams(0, teams);
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+
+    let user = game.players[0].roster.active().unwrap();
+    assert_eq!(user.stats[StatId::Atk].stage(), 0);
+    assert_eq!(user.stats[StatId::Def].stage(), 0);
+    assert_eq!(*user.item.borrow(), None);
+    assert_eq!(user.consumed, Some(Item::WhiteHerb));
+
+    // both drops land before the herb activates once
+    let log = game.log.last().unwrap();
+    let herb = log
+        .iter()
+        .position(|event| matches!(event, BattleEvent::ItemActivated { .. }))
+        .unwrap();
+    let drops: Vec<usize> = (0..log.len())
+        .filter(|&i| matches!(log[i], BattleEvent::StatChanged { stages: -1, .. }))
+        .collect();
+    assert_eq!(drops.len(), 2);
+    assert!(drops.iter().all(|&i| i < herb));
+}
+
+fn item_game(user: &str, target: &str) -> Game {
+    Game::with_teams(0, [parse_team(user).unwrap(), parse_team(target).unwrap()])
+}
+
+fn held(game: &Game, player: usize) -> Option<Item> {
+    *game.players[player].roster.active().unwrap().item.borrow()
+}
+
+#[test]
+fn thief_steals_the_targets_item() {
+    let mut game = item_game("Starmie\n- Thief", "Jirachi @ Leftovers\n- Refresh");
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+
+    assert_eq!(held(&game, 0), Some(Item::Leftovers));
+    assert_eq!(held(&game, 1), None);
+}
+
+#[test]
+fn trick_locks_the_target_into_its_next_move() {
+    let mut game = item_game(
+        "Starmie @ Choice Scarf\n- Trick\n- Hydro Pump",
+        "Jirachi @ Leftovers\n- Refresh\n- Iron Head",
+    );
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+
+    assert_eq!(held(&game, 0), Some(Item::Leftovers));
+    assert_eq!(held(&game, 1), Some(Item::ChoiceScarf));
+    let bot = &game.players[1];
+    let moves: Vec<MoveSelection> = bot
+        .list_valid_inputs(&game.state)
+        .into_iter()
+        .filter(|input| matches!(input, MoveSelection::Move(_)))
+        .collect();
+    assert_eq!(moves, vec![*bot.inputs.last().unwrap()]);
+}
+
+#[test]
+fn knocked_off_mon_cannot_get_an_item_back() {
+    // Tyranitar is slower, so Jirachi's first Thief fails while it still holds Leftovers
+    let mut game = item_game(
+        "Jirachi @ Leftovers\n- Thief\n- Trick",
+        "Tyranitar @ Leftovers\n- Knock Off",
+    );
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+    assert_eq!(held(&game, 0), None);
+    assert!(game.players[0].roster.active().unwrap().item_knocked_off);
+
+    game.players[0].inputs.push(MoveSelection::Move(0));
+    game.execute_turn();
+    assert_eq!(held(&game, 0), None);
+    assert_eq!(held(&game, 1), Some(Item::Leftovers));
+
+    game.players[0].inputs.push(MoveSelection::Move(1));
+    game.execute_turn();
+    assert_eq!(held(&game, 0), None);
+    assert!(game.log.last().unwrap().contains(&BattleEvent::MoveFailed));
+
+    // switching out lifts the restriction
+    let mon = game.players[0].roster.active_mut().unwrap();
+    mon.switch_out();
+    assert!(!mon.item_knocked_off);
+}
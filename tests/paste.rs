@@ -0,0 +1,71 @@
+use pkml::{
+    moves::MoveId,
+    paste::{export_team, parse_team},
+    player::default_team,
+    preset::PokeId,
+    stat::Nature,
+    trigger::{Ability, Item},
+};
+
+#[test]
+fn default_team_round_trips() {
+    let team = default_team();
+    assert_eq!(parse_team(&export_team(&team)).unwrap(), team);
+}
+
+#[test]
+fn full_set() {
+    let team = parse_team(
+        "=== [gen4ou] Sand ===\n\
+         \n\
+         Tyrant (Tyranitar) (M) @ Choice Band\n\
+         Ability: Sand Stream\n\
+         Level: 50\n\
+         Shiny: Yes\n\
+         EVs: 252 Atk / 4 SpD / 252 Spe\n\
+         Adamant Nature\n\
+         IVs: 30 Def / 0 SpA\n\
+         - Crunch\n\
+         - Hidden Power [Ice]\n\
+         \n\
+         Zapdos (F)\n\
+         - Roost",
+    )
+    .unwrap();
+    assert_eq!(team.len(), 2);
+
+    let set = &team[0];
+    assert_eq!(set.species, PokeId::from_name("Tyranitar").unwrap());
+    assert_eq!(set.item, Some(Item::ChoiceBand));
+    assert_eq!(set.ability, Ability::SandStream);
+    assert_eq!(set.spread.level, 50);
+    assert_eq!(set.spread.evs, [0, 252, 0, 0, 4, 252]);
+    assert_eq!(set.spread.ivs, [31, 31, 30, 0, 31, 31]);
+    assert_eq!(set.spread.nature, Nature::Adamant);
+    assert_eq!(
+        set.moves,
+        vec![
+            MoveId::from_name("Crunch").unwrap(),
+            MoveId::from_name("Hidden Power Ice").unwrap()
+        ]
+    );
+
+    // anything left out falls back to the preset ability and a plain spread
+    let set = &team[1];
+    assert_eq!(set.species, PokeId::from_name("Zapdos").unwrap());
+    assert_eq!(set.item, None);
+    assert_eq!(set.ability, Ability::Pressure);
+    assert_eq!(set.spread.evs, [0; 6]);
+}
+
+#[test]
+fn errors() {
+    let err = |text: &str| parse_team(text).unwrap_err();
+
+    assert!(err("Missingno\n- Crunch").contains("unknown species Missingno"));
+    assert!(err("Tyranitar @ Gold Bottle Cap\n- Crunch").contains("unknown item"));
+    assert!(err("Tyranitar\n- Hyper Beam").contains("unknown move Hyper Beam"));
+    assert!(err("Tyranitar\n- Crunch\n\n".repeat(7).as_str()).contains("team has 7 sets"));
+    assert!(err("Tyranitar\nEVs: 252 Atk / 252 Spe / 252 HP\n- Crunch").contains("510"));
+    assert!(err("").contains("team has 0 sets"));
+}
@@ -0,0 +1,11 @@
+use pkml::{replay::Replay, sim::run_game};
+
+#[test]
+fn replay_reproduces_log() {
+    for seed in 0..20 {
+        let game = run_game(seed);
+        let replayed = Replay::from(&game).play().unwrap();
+        assert_eq!(replayed.log, game.log, "seed {} diverged", seed);
+        assert_eq!(replayed.state, game.state);
+    }
+}
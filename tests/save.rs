@@ -0,0 +1,39 @@
+use pkml::{
+    game::{Game, GameState},
+    save::Save,
+};
+
+/// feeds player 1 a fixed pattern of valid inputs
+fn play_turn(game: &mut Game) {
+    let choices = game.players[0].list_valid_inputs(&game.state);
+    let input = choices[game.turn_count as usize % choices.len()];
+    game.players[0].inputs.push(input);
+    game.execute_turn();
+}
+
+#[test]
+fn restored_save_plays_on_identically() {
+    for seed in 0..20 {
+        let mut game = Game::with_seed(seed);
+        for _ in 0..(seed % 7 + 3) {
+            if matches!(game.state, GameState::Completed(_)) {
+                break;
+            }
+            play_turn(&mut game);
+        }
+
+        let json = serde_json::to_value(Save::from(&game)).unwrap();
+        let mut restored = serde_json::from_value::<Save>(json.clone())
+            .unwrap()
+            .restore()
+            .unwrap();
+        assert_eq!(serde_json::to_value(Save::from(&restored)).unwrap(), json);
+
+        while !matches!(game.state, GameState::Completed(_)) && game.turn_count < 200 {
+            play_turn(&mut game);
+            play_turn(&mut restored);
+        }
+        assert_eq!(restored.log, game.log, "seed {} diverged", seed);
+        assert_eq!(restored.state, game.state);
+    }
+}
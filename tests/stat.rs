@@ -0,0 +1,128 @@
+use std::{cell::RefCell, rc::Rc};
+
+use pkml::{
+    stat::{accuracy_fraction, stage_fraction, Nature, Spread, Stat, StatId},
+    status::StatusBlock,
+    trigger::Ability,
+};
+
+fn stat(value: i32, id: StatId) -> Stat {
+    Stat::new(
+        value,
+        id,
+        Rc::new(RefCell::new(None)),
+        Ability::default(),
+        Rc::new(RefCell::new(None)),
+        Rc::new(RefCell::new(StatusBlock::new())),
+    )
+}
+
+#[test]
+fn stage_fractions() {
+    let expected = [
+        (2, 8),
+        (2, 7),
+        (2, 6),
+        (2, 5),
+        (2, 4),
+        (2, 3),
+        (2, 2),
+        (3, 2),
+        (4, 2),
+        (5, 2),
+        (6, 2),
+        (7, 2),
+        (8, 2),
+    ];
+    for (stage, fraction) in (-6..=6).zip(expected) {
+        assert_eq!(stage_fraction(stage), fraction, "stage {}", stage);
+    }
+}
+
+#[test]
+fn accuracy_fractions() {
+    let expected = [
+        (3, 9),
+        (3, 8),
+        (3, 7),
+        (3, 6),
+        (3, 5),
+        (3, 4),
+        (3, 3),
+        (4, 3),
+        (5, 3),
+        (6, 3),
+        (7, 3),
+        (8, 3),
+        (9, 3),
+    ];
+    for (stage, fraction) in (-6..=6).zip(expected) {
+        assert_eq!(accuracy_fraction(stage), fraction, "stage {}", stage);
+    }
+}
+
+#[test]
+fn every_stat_stage() {
+    let atk = stat(100, StatId::Atk);
+    let expected = [25, 28, 33, 40, 50, 66, 100, 150, 200, 250, 300, 350, 400];
+    for (stage, value) in (-6..=6).zip(expected) {
+        assert_eq!(atk.with_stage(stage), value, "stage {}", stage);
+    }
+}
+
+#[test]
+fn every_accuracy_stage() {
+    let acc = stat(100, StatId::Acc);
+    let expected = [33, 37, 42, 50, 60, 75, 100, 133, 166, 200, 233, 266, 300];
+    for (stage, value) in (-6..=6).zip(expected) {
+        assert_eq!(acc.with_stage(stage), value, "stage {}", stage);
+    }
+}
+
+// preset Jirachi's 249 Atk at +1 truncates rather than rounding
+#[test]
+fn stages_truncate() {
+    let atk = stat(249, StatId::Atk);
+    assert_eq!(atk.with_stage(1), 373);
+    assert_eq!(atk.with_stage(-1), 166);
+}
+
+#[test]
+fn alter_reports_change() {
+    let mut atk = stat(100, StatId::Atk);
+    assert!(atk.alter(2));
+    assert_eq!(atk.stage(), 2);
+    assert_eq!(atk.curr(), 200);
+
+    assert!(atk.alter(6));
+    assert_eq!(atk.stage(), 6);
+    assert!(!atk.alter(1));
+    assert_eq!(atk.stage(), 6);
+
+    assert!(atk.alter(-12));
+    assert!(!atk.alter(-1));
+    assert_eq!(atk.curr(), 25);
+}
+
+// Lv. 78 Adamant Garchomp, the worked example from Bulbapedia
+#[test]
+fn bulbapedia_spread() {
+    let spread = Spread {
+        level: 78,
+        evs: [74, 190, 91, 48, 84, 23],
+        ivs: [24, 12, 30, 16, 23, 5],
+        nature: Nature::Adamant,
+    };
+    assert_eq!(
+        spread.calc([108, 130, 95, 80, 85, 102]),
+        [289, 278, 193, 135, 171, 171]
+    );
+}
+
+#[test]
+fn nature_modifiers() {
+    assert_eq!(Nature::Timid.modifier(StatId::Spe), 110);
+    assert_eq!(Nature::Timid.modifier(StatId::Atk), 90);
+    assert_eq!(Nature::Timid.modifier(StatId::Spa), 100);
+    assert_eq!(Nature::Serious.modifier(StatId::Spe), 100);
+}